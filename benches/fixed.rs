@@ -0,0 +1,69 @@
+//! Compares the fixed-size one-shot functions against the generic paths, on
+//! the integer keys a `HashMap` hashes.
+//!
+//! Run with `cargo bench --bench fixed`.
+
+use std::hash::BuildHasher;
+use std::hint::black_box;
+use std::time::Instant;
+
+use const_siphasher::sip::{SipBuildHasher13, SipHasher13};
+use const_siphasher::sip128;
+
+const ITERATIONS: u32 = 10_000_000;
+
+fn bench(name: &str, mut f: impl FnMut(u64) -> u64) {
+    let start = Instant::now();
+    let mut acc = 0;
+    for i in 0..ITERATIONS {
+        acc ^= f(black_box(i as u64));
+    }
+    black_box(acc);
+    println!(
+        "{:<40} {:>8.2} ns/hash",
+        name,
+        start.elapsed().as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    let builder = SipBuildHasher13::new_with_keys(1, 2);
+    let hasher = SipHasher13::new_with_keys(1, 2);
+
+    bench("u32: BuildHasher::hash_one", |i| builder.hash_one(i as u32));
+    bench("u32: hash(&to_le_bytes())", |i| {
+        hasher.hash(&(i as u32).to_le_bytes())
+    });
+    bench("u32: hash_u32", |i| hasher.hash_u32(i as u32));
+
+    bench("u64: BuildHasher::hash_one", |i| builder.hash_one(i));
+    bench("u64: hash(&to_le_bytes())", |i| {
+        hasher.hash(&i.to_le_bytes())
+    });
+    bench("u64: hash_u64", |i| hasher.hash_u64(i));
+
+    bench("u128: BuildHasher::hash_one", |i| {
+        builder.hash_one(i as u128 * 3)
+    });
+    bench("u128: hash(&to_le_bytes())", |i| {
+        hasher.hash(&(i as u128 * 3).to_le_bytes())
+    });
+    bench("u128: hash_u128", |i| hasher.hash_u128(i as u128 * 3));
+
+    bench("[u8; 20]: hash", |i| {
+        let mut bytes = [0; 20];
+        bytes[..8].copy_from_slice(&i.to_le_bytes());
+        hasher.hash(black_box(&bytes))
+    });
+    bench("[u8; 20]: hash_array", |i| {
+        let mut bytes = [0; 20];
+        bytes[..8].copy_from_slice(&i.to_le_bytes());
+        hasher.hash_array(black_box(&bytes))
+    });
+
+    let hasher128 = sip128::SipHasher13::new_with_keys(1, 2);
+    bench("sip128 u64: hash(&to_le_bytes())", |i| {
+        hasher128.hash(&i.to_le_bytes()).h1
+    });
+    bench("sip128 u64: hash_u64", |i| hasher128.hash_u64(i).h1);
+}
@@ -0,0 +1,43 @@
+//! Compares `KeyedSip13` against `SipHasher13::hash` on short messages.
+//!
+//! Run with `cargo bench --bench keyed`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use const_siphasher::sip::{KeyedSip13, SipHasher13};
+
+const ITERATIONS: u32 = 10_000_000;
+
+fn bench(name: &str, mut f: impl FnMut(u64) -> u64) {
+    let start = Instant::now();
+    let mut acc = 0;
+    for i in 0..ITERATIONS {
+        acc ^= f(black_box(i as u64));
+    }
+    black_box(acc);
+    let elapsed: Duration = start.elapsed();
+    println!(
+        "{:<32} {:>8.2} ns/hash",
+        name,
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    let hasher = SipHasher13::new_with_keys(1, 2);
+    let keyed = KeyedSip13::new_with_keys(1, 2);
+
+    for len in [3, 8, 16, 32] {
+        let msg = vec![0x5a; len];
+        bench(&format!("SipHasher13::hash ({} bytes)", len), |i| {
+            hasher.hash(black_box(&msg)) ^ i
+        });
+        bench(&format!("KeyedSip13::hash ({} bytes)", len), |i| {
+            keyed.hash(black_box(&msg)) ^ i
+        });
+    }
+
+    bench("SipHasher13::hash (u64)", |i| hasher.hash(&i.to_le_bytes()));
+    bench("KeyedSip13::hash_u64", |i| keyed.hash_u64(i));
+}
@@ -0,0 +1,64 @@
+//! Compares `hash_many` against hashing the same short strings one at a time.
+//!
+//! Run with `cargo bench --bench many`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use const_siphasher::sip::SipHasher13;
+
+const ROUNDS: u32 = 1_000;
+
+fn bench(name: &str, count: usize, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    println!(
+        "{:<36} {:>8.2} ns/hash",
+        name,
+        start.elapsed().as_nanos() as f64 / (ROUNDS as usize * count) as f64
+    );
+}
+
+fn main() {
+    let hasher = SipHasher13::new_with_keys(1, 2);
+
+    for (min, max) in [(1, 8), (8, 24), (24, 64)] {
+        let strings: Vec<Vec<u8>> = (0..10_000)
+            .map(|i: usize| {
+                let len = min + i.wrapping_mul(2654435761) % (max - min);
+                (0..len).map(|j| (i + j) as u8).collect()
+            })
+            .collect();
+        let inputs: Vec<&[u8]> = strings.iter().map(|s| &s[..]).collect();
+        let mut out = vec![0; inputs.len()];
+
+        bench(
+            &format!("hash ({}..{} bytes)", min, max),
+            inputs.len(),
+            || {
+                for (input, out) in inputs.iter().zip(out.iter_mut()) {
+                    *out = hasher.hash(black_box(input));
+                }
+                black_box(&out);
+            },
+        );
+        bench(
+            &format!("hash_many_scalar ({}..{} bytes)", min, max),
+            inputs.len(),
+            || {
+                hasher.hash_many_scalar(black_box(&inputs), &mut out);
+                black_box(&out);
+            },
+        );
+        bench(
+            &format!("hash_many ({}..{} bytes)", min, max),
+            inputs.len(),
+            || {
+                hasher.hash_many(black_box(&inputs), &mut out);
+                black_box(&out);
+            },
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Derive macro for the portable hashing of `const-siphasher`.
+//!
+//! This crate is re-exported by `const-siphasher` when its `derive` feature is
+//! enabled, and shouldn't be used directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Index};
+
+/// Derives `const_siphasher::ConstHash` with a canonical, portable encoding.
+///
+/// Fields are hashed in declaration order with their own `ConstHash`
+/// implementations. Enums first write the discriminant of the variant, cast
+/// to a little-endian `u32`, followed by the fields of that variant. As in
+/// Rust, a variant without an explicit discriminant takes the discriminant of
+/// the previous variant plus one, or zero for the first one. Reordering
+/// fields, or variants without explicit discriminants, thus changes the
+/// hashes, but the platform never does.
+///
+/// Discriminants that don't fit in a `u32`, such as negative ones, are
+/// rejected at compile time:
+///
+/// ```compile_fail
+/// use const_siphasher::PortableSipHash;
+///
+/// #[derive(PortableSipHash)]
+/// #[repr(i64)]
+/// enum Signed {
+///     Negative = -1,
+///     Zero = 0,
+/// }
+/// ```
+#[proc_macro_derive(PortableSipHash)]
+pub fn derive_portable_sip_hash(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(param) = param {
+            param
+                .bounds
+                .push(parse_quote!(::const_siphasher::ConstHash));
+        }
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, writes) = destructure(&data.fields);
+            quote! {
+                let Self #pattern = self;
+                #(#writes)*
+            }
+        }
+        Data::Enum(data) => {
+            // The last explicit discriminant, and how many variants follow it.
+            let mut base = None;
+            let mut offset = 0u32;
+            let arms = data.variants.iter().map(|variant| {
+                if let Some((_, expr)) = &variant.discriminant {
+                    base = Some(expr);
+                    offset = 0;
+                }
+                // Explicit discriminants are checked at compile time, so that
+                // two variants can't be hashed the same after truncation.
+                let discriminant = match base {
+                    Some(expr) => quote! {{
+                        const DISCRIMINANT: u32 = {
+                            let discriminant = (#expr) as i128 + #offset as i128;
+                            assert!(
+                                discriminant >= 0 && discriminant <= u32::MAX as i128,
+                                "PortableSipHash needs discriminants that fit in a u32",
+                            );
+                            discriminant as u32
+                        };
+                        DISCRIMINANT
+                    }},
+                    None => quote!(#offset),
+                };
+                offset = offset.wrapping_add(1);
+                let ident = &variant.ident;
+                let (pattern, writes) = destructure(&variant.fields);
+                quote! {
+                    Self::#ident #pattern => {
+                        let discriminant: u32 = #discriminant;
+                        ::const_siphasher::ConstHash::const_hash(&discriminant, state);
+                        #(#writes)*
+                    }
+                }
+            });
+            let arms: Vec<_> = arms.collect();
+            // An empty enum has no values, and `&Self` can't be matched
+            // exhaustively with no arms.
+            if data.variants.is_empty() {
+                quote!(match *self {})
+            } else {
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "PortableSipHash can't be derived for unions",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::const_siphasher::ConstHash for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn const_hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                #body
+            }
+        }
+    })
+}
+
+/// Returns a pattern binding every field, and the statements hashing them.
+fn destructure(fields: &Fields) -> (TokenStream2, Vec<TokenStream2>) {
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let writes = bindings
+        .iter()
+        .map(|binding| quote!(::const_siphasher::ConstHash::const_hash(#binding, state);))
+        .collect();
+    let pattern = match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => {
+            let indices = (0..fields.len()).map(Index::from);
+            quote!({ #(#indices: #bindings),* })
+        }
+        Fields::Unit => quote!(),
+    };
+    (pattern, writes)
+}
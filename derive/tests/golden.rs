@@ -0,0 +1,125 @@
+use const_siphasher::sip::SipHasher24;
+use const_siphasher::sip128::SipHasher13;
+use const_siphasher::{ConstHash, HashValue, PortableSipHash};
+
+#[derive(PortableSipHash)]
+struct Unit;
+
+#[derive(PortableSipHash)]
+struct Record {
+    id: u32,
+    name: String,
+    tags: Vec<&'static str>,
+    offset: isize,
+    enabled: bool,
+}
+
+#[derive(PortableSipHash)]
+struct Pair<T>(T, T);
+
+#[derive(PortableSipHash)]
+enum Shape {
+    Empty,
+    Circle { radius: u16 },
+    Polygon(Vec<(i32, i32)>),
+}
+
+#[derive(PortableSipHash)]
+enum Never {}
+
+#[derive(PortableSipHash)]
+#[allow(dead_code)]
+enum Pinned {
+    B = 5,
+    A = 2,
+    C,
+}
+
+#[derive(PortableSipHash)]
+#[repr(u64)]
+enum Wide {
+    Max = 0xffff_ffff,
+}
+
+#[derive(PortableSipHash)]
+#[repr(u8)]
+enum PinnedFields {
+    Empty = 10,
+    Value(u8),
+}
+
+fn hash64<T: ConstHash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = SipHasher24::new_with_keys(1, 2);
+    value.const_hash(&mut hasher);
+    hasher.finish()
+}
+
+fn record() -> Record {
+    Record {
+        id: 7,
+        name: "foo".to_string(),
+        tags: vec!["a", "bc"],
+        offset: -1,
+        enabled: true,
+    }
+}
+
+#[test]
+fn test_derive_encoding() {
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&7u32.to_le_bytes());
+    bytes.extend_from_slice(&3u64.to_le_bytes());
+    bytes.extend_from_slice(b"foo");
+    bytes.extend_from_slice(&2u64.to_le_bytes());
+    bytes.extend_from_slice(&1u64.to_le_bytes());
+    bytes.extend_from_slice(b"a");
+    bytes.extend_from_slice(&2u64.to_le_bytes());
+    bytes.extend_from_slice(b"bc");
+    bytes.extend_from_slice(&(-1i64).to_le_bytes());
+    bytes.push(1);
+    assert_eq!(
+        hash64(&record()),
+        SipHasher24::new_with_keys(1, 2).hash(&bytes)
+    );
+
+    const SHAPE: u64 = SipHasher24::new_with_keys(1, 2)
+        .hash_value(&HashValue::Tuple(&[HashValue::U32(1), HashValue::U16(5)]));
+    assert_eq!(hash64(&Shape::Circle { radius: 5 }), SHAPE);
+
+    assert_eq!(hash64(&Pair(1u8, 2u8)), hash64(&(1u8, 2u8)));
+    assert_eq!(hash64(&Unit), hash64(&()));
+    assert_eq!(hash64(&Some(Unit)), hash64(&1u32));
+    let _ = |never: &Never| hash64(never);
+}
+
+#[test]
+fn test_derive_explicit_discriminants() {
+    // Explicit discriminants are hashed, not the position of the variants,
+    // and the following variants count from them.
+    assert_eq!(hash64(&Pinned::B), hash64(&5u32));
+    assert_eq!(hash64(&Pinned::A), hash64(&2u32));
+    assert_eq!(hash64(&Pinned::C), hash64(&3u32));
+    assert_eq!(hash64(&PinnedFields::Empty), hash64(&10u32));
+    assert_eq!(hash64(&PinnedFields::Value(7)), hash64(&(11u32, 7u8)));
+    assert_eq!(hash64(&Wide::Max), hash64(&u32::MAX));
+}
+
+#[test]
+fn test_derive_golden() {
+    assert_eq!(hash64(&Unit), 0x8628af35e1cba77b);
+    assert_eq!(hash64(&record()), 0x7c2331b7498b10d0);
+    assert_eq!(hash64(&Pair(-3i8, 4)), 0xb121b5e253b166c5);
+    assert_eq!(hash64(&Shape::Empty), 0x0f5525364d491e13);
+    assert_eq!(hash64(&Shape::Circle { radius: 5 }), 0x03c1a239756cb9af);
+    assert_eq!(
+        hash64(&Shape::Polygon(vec![(0, 0), (1, -1)])),
+        0x3ff02718d5f601e7
+    );
+
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    record().const_hash(&mut hasher);
+    assert_eq!(
+        hasher.finish128().as_u128(),
+        0x902ecd4a8d1954414386bc32a79ffaf0
+    );
+}
@@ -0,0 +1,626 @@
+//! Keyed Bloom filters.
+//!
+//! Every item is hashed once with a 128-bit SipHash-1-3, and its `k` bits are
+//! derived from the two halves `h1` and `h2` of the output by double hashing
+//! (Kirsch and Mitzenmacher): the `i`-th bit is `(h1 + i * h2) mod m`, for a
+//! filter of `m` bits. Keying the hash with a secret [`SipKey`] keeps an
+//! attacker from crafting items that all set the same bits.
+//!
+//! [`BloomFilter`] has a fixed size and no allocation, and can be built in a
+//! `const` context. With the `alloc` feature, [`GrowableBloomFilter`] adds
+//! storage as items are inserted, keeping its false positive rate below a
+//! target.
+//!
+//! ```rust
+//! use const_siphasher::bloom::BloomFilter;
+//!
+//! let mut filter = BloomFilter::<16, 5>::new_with_keys(1, 2);
+//! filter.insert(b"foo");
+//! assert!(filter.contains(b"foo"));
+//! assert!(!filter.contains(b"bar"));
+//! ```
+
+use core::fmt;
+
+use crate::sip128::{Hash128, SipHasher13};
+use crate::SipKey;
+
+/// Returns the `i`-th bit of an item hashing to `h`, in a filter of `bits`
+/// bits.
+///
+/// `h2` is made odd, so that the bits are all different when `bits` is a
+/// power of two.
+#[inline]
+const fn bit_index(h: Hash128, i: usize, bits: usize) -> usize {
+    let step = h.h2 | 1;
+    (h.h1.wrapping_add((i as u64).wrapping_mul(step)) % bits as u64) as usize
+}
+
+/// Sets the `k` bits of `h` in `words`, returning `true` if any was unset.
+#[inline]
+const fn set_bits(words: &mut [u64], h: Hash128, k: usize) -> bool {
+    let bits = words.len() * 64;
+    let mut changed = false;
+    let mut i = 0;
+    while i < k {
+        let bit = bit_index(h, i, bits);
+        let mask = 1 << (bit % 64);
+        changed |= words[bit / 64] & mask == 0;
+        words[bit / 64] |= mask;
+        i += 1;
+    }
+    changed
+}
+
+/// Returns `true` if the `k` bits of `h` are set in `words`.
+#[inline]
+const fn has_bits(words: &[u64], h: Hash128, k: usize) -> bool {
+    let bits = words.len() * 64;
+    let mut i = 0;
+    while i < k {
+        let bit = bit_index(h, i, bits);
+        if words[bit / 64] & (1 << (bit % 64)) == 0 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn same_key(a: SipKey, b: SipKey) -> bool {
+    let (a0, a1) = a.to_u64s();
+    let (b0, b1) = b.to_u64s();
+    a0 == b0 && a1 == b1
+}
+
+/// A Bloom filter of `64 * WORDS` bits, setting `K` bits per item.
+///
+/// The size is given in 64-bit words, as the bits are stored in a
+/// `[u64; WORDS]` and stable Rust can't compute an array length from a
+/// generic bit count. For `n` items, the false positive rate is about
+/// `(1 - e^(-K * n / m))^K` with `m = 64 * WORDS`, and is lowest for
+/// `K ≈ 0.69 * m / n`.
+///
+/// Filters are only comparable, and can only be merged, if they have the same
+/// key.
+#[derive(Clone)]
+pub struct BloomFilter<const WORDS: usize, const K: usize> {
+    key: SipKey,
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize, const K: usize> BloomFilter<WORDS, K> {
+    /// The number of bits of the filter.
+    pub const BITS: usize = 64 * WORDS;
+
+    /// Creates an empty filter with the two keys set to 0.
+    #[inline]
+    pub const fn new() -> BloomFilter<WORDS, K> {
+        BloomFilter::new_with_keys(0, 0)
+    }
+
+    /// Creates an empty filter keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> BloomFilter<WORDS, K> {
+        BloomFilter::new_with_sip_key(SipKey::from_u64s(key0, key1))
+    }
+
+    /// Creates an empty filter keyed off a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> BloomFilter<WORDS, K> {
+        BloomFilter::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates an empty filter keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> BloomFilter<WORDS, K> {
+        BloomFilter::from_words(key, [0; WORDS])
+    }
+
+    /// Rebuilds a filter from its key and the words returned by
+    /// [`words`](Self::words).
+    ///
+    /// # Panics
+    ///
+    /// If `WORDS` or `K` is zero. The other constructors panic in the same
+    /// case.
+    #[inline]
+    pub const fn from_words(key: SipKey, words: [u64; WORDS]) -> BloomFilter<WORDS, K> {
+        assert!(WORDS > 0, "a Bloom filter needs at least one word");
+        assert!(K > 0, "a Bloom filter needs at least one bit per item");
+        BloomFilter { key, words }
+    }
+
+    /// Returns the key of the filter.
+    #[inline]
+    pub const fn sip_key(&self) -> SipKey {
+        self.key
+    }
+
+    /// Returns the bits of the filter.
+    #[inline]
+    pub const fn words(&self) -> &[u64; WORDS] {
+        &self.words
+    }
+
+    #[inline]
+    const fn hash(&self, item: &[u8]) -> Hash128 {
+        SipHasher13::new_with_sip_key(self.key).hash(item)
+    }
+
+    /// Adds `item` to the filter.
+    ///
+    /// Returns `false` if the item may already have been in the filter, and
+    /// `true` if it certainly wasn't.
+    #[inline]
+    pub const fn insert(&mut self, item: &[u8]) -> bool {
+        let h = self.hash(item);
+        set_bits(&mut self.words, h, K)
+    }
+
+    /// Returns `true` if `item` may be in the filter, and `false` if it
+    /// certainly isn't.
+    #[inline]
+    pub const fn contains(&self, item: &[u8]) -> bool {
+        has_bits(&self.words, self.hash(item), K)
+    }
+
+    /// Adds the items of `other` to the filter.
+    ///
+    /// # Panics
+    ///
+    /// If the filters don't have the same key.
+    pub const fn union(&mut self, other: &BloomFilter<WORDS, K>) {
+        assert!(
+            same_key(self.key, other.key),
+            "Bloom filters with different keys can't be merged"
+        );
+        let mut i = 0;
+        while i < WORDS {
+            self.words[i] |= other.words[i];
+            i += 1;
+        }
+    }
+
+    /// Removes every item from the filter.
+    #[inline]
+    pub const fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    /// Returns `true` if no item was inserted.
+    pub const fn is_empty(&self) -> bool {
+        let mut i = 0;
+        while i < WORDS {
+            if self.words[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns the number of bits set.
+    pub const fn count_ones(&self) -> usize {
+        let mut ones = 0;
+        let mut i = 0;
+        while i < WORDS {
+            ones += self.words[i].count_ones() as usize;
+            i += 1;
+        }
+        ones
+    }
+}
+
+impl<const WORDS: usize, const K: usize> PartialEq for BloomFilter<WORDS, K> {
+    fn eq(&self, other: &BloomFilter<WORDS, K>) -> bool {
+        same_key(self.key, other.key) && self.words == other.words
+    }
+}
+
+impl<const WORDS: usize, const K: usize> Eq for BloomFilter<WORDS, K> {}
+
+impl<const WORDS: usize, const K: usize> Default for BloomFilter<WORDS, K> {
+    #[inline]
+    fn default() -> BloomFilter<WORDS, K> {
+        BloomFilter::new()
+    }
+}
+
+impl<const WORDS: usize, const K: usize> fmt::Debug for BloomFilter<WORDS, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BloomFilter")
+            .field("bits", &Self::BITS)
+            .field("k", &K)
+            .field("ones", &self.count_ones())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const WORDS: usize, const K: usize> serde::Serialize for BloomFilter<WORDS, K> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("BloomFilter", 2)?;
+        state.serialize_field("key", &self.key)?;
+        state.serialize_field("words", &self.words[..])?;
+        state.end()
+    }
+}
+
+/// The words of a [`BloomFilter`], deserialized from a sequence of exactly
+/// `WORDS` elements.
+#[cfg(feature = "serde")]
+struct Words<const WORDS: usize>([u64; WORDS]);
+
+#[cfg(feature = "serde")]
+impl<'de, const WORDS: usize> serde::Deserialize<'de> for Words<WORDS> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, SeqAccess, Visitor};
+
+        struct WordsVisitor<const WORDS: usize>;
+
+        impl<'de, const WORDS: usize> Visitor<'de> for WordsVisitor<WORDS> {
+            type Value = Words<WORDS>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a sequence of {} words", WORDS)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Words<WORDS>, A::Error> {
+                let mut words = [0; WORDS];
+                for (i, word) in words.iter_mut().enumerate() {
+                    *word = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(WORDS + 1, &self));
+                }
+                Ok(Words(words))
+            }
+        }
+
+        deserializer.deserialize_seq(WordsVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const WORDS: usize, const K: usize> serde::Deserialize<'de> for BloomFilter<WORDS, K> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "BloomFilter")]
+        struct Repr<const WORDS: usize> {
+            key: SipKey,
+            words: Words<WORDS>,
+        }
+
+        if WORDS == 0 {
+            return Err(serde::de::Error::custom(
+                "a Bloom filter needs at least one word",
+            ));
+        }
+        if K == 0 {
+            return Err(serde::de::Error::custom(
+                "a Bloom filter needs at least one bit per item",
+            ));
+        }
+        let Repr { key, words } = Repr::deserialize(deserializer)?;
+        Ok(BloomFilter {
+            key,
+            words: words.0,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::growable::GrowableBloomFilter;
+
+#[cfg(feature = "alloc")]
+mod growable {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    #[cfg(feature = "serde")]
+    use core::convert::TryFrom;
+    use core::fmt;
+
+    use super::{has_bits, same_key, set_bits};
+    use crate::sip128::SipHasher13;
+    use crate::SipKey;
+
+    /// One of the filters of a [`GrowableBloomFilter`], holding `capacity`
+    /// items with `k` bits each.
+    #[derive(Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    struct Stage {
+        k: usize,
+        capacity: usize,
+        len: usize,
+        words: Vec<u64>,
+    }
+
+    impl Stage {
+        /// Creates a stage with about `k / ln 2` bits per item, for a false
+        /// positive rate of about `2^-k` once full.
+        fn new(capacity: usize, k: usize) -> Stage {
+            let words = Stage::words(capacity, k).expect("the Bloom filter is too large");
+            Stage {
+                k,
+                capacity,
+                len: 0,
+                words: vec![0; words],
+            }
+        }
+
+        /// Returns the number of words of a stage, or `None` on overflow.
+        fn words(capacity: usize, k: usize) -> Option<usize> {
+            let bits = k.checked_mul(capacity)?.checked_mul(1443)?.div_ceil(1000);
+            Some(bits.div_ceil(64).max(1))
+        }
+    }
+
+    /// A Bloom filter growing as items are inserted (a "scalable Bloom filter",
+    /// Almeida et al.).
+    ///
+    /// The filter is a list of stages. Once a stage holds as many items as it
+    /// was sized for, a new stage twice as large and with one more bit per
+    /// item is added, so the false positive rate of every stage is half the
+    /// one of the previous stage, and their sum stays below the target rate.
+    ///
+    /// ```rust
+    /// use const_siphasher::bloom::GrowableBloomFilter;
+    ///
+    /// let mut filter = GrowableBloomFilter::new_with_keys(1, 2, 100, 0.01);
+    /// for i in 0..1000u32 {
+    ///     filter.insert(&i.to_le_bytes());
+    /// }
+    /// assert!(filter.contains(&7u32.to_le_bytes()));
+    /// assert_eq!(filter.stages(), 4);
+    /// ```
+    #[derive(Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(try_from = "RawGrowableBloomFilter"))]
+    pub struct GrowableBloomFilter {
+        key: SipKey,
+        initial_capacity: usize,
+        initial_k: usize,
+        stages: Vec<Stage>,
+    }
+
+    /// A deserialized [`GrowableBloomFilter`], not yet validated.
+    #[cfg(feature = "serde")]
+    #[derive(serde::Deserialize)]
+    struct RawGrowableBloomFilter {
+        key: SipKey,
+        initial_capacity: usize,
+        initial_k: usize,
+        stages: Vec<Stage>,
+    }
+
+    #[cfg(feature = "serde")]
+    impl TryFrom<RawGrowableBloomFilter> for GrowableBloomFilter {
+        type Error = &'static str;
+
+        fn try_from(raw: RawGrowableBloomFilter) -> Result<GrowableBloomFilter, &'static str> {
+            if raw.initial_capacity == 0 || raw.initial_k == 0 {
+                return Err("invalid initial capacity or number of bits per item");
+            }
+            if raw.stages.is_empty() {
+                return Err("a Bloom filter needs at least one stage");
+            }
+            // Every stage is twice as large as the previous one, with one more
+            // bit per item.
+            for (i, stage) in raw.stages.iter().enumerate() {
+                let capacity = u32::try_from(i)
+                    .ok()
+                    .and_then(|i| raw.initial_capacity.checked_shl(i))
+                    .filter(|capacity| capacity >> i == raw.initial_capacity);
+                let k = raw.initial_k.checked_add(i);
+                let words = capacity
+                    .zip(k)
+                    .and_then(|(capacity, k)| Stage::words(capacity, k));
+                if Some(stage.capacity) != capacity
+                    || Some(stage.k) != k
+                    || Some(stage.words.len()) != words
+                    || stage.len > stage.capacity
+                {
+                    return Err("invalid stage");
+                }
+            }
+            Ok(GrowableBloomFilter {
+                key: raw.key,
+                initial_capacity: raw.initial_capacity,
+                initial_k: raw.initial_k,
+                stages: raw.stages,
+            })
+        }
+    }
+
+    impl GrowableBloomFilter {
+        /// Creates an empty filter with the two keys set to 0.
+        ///
+        /// The first stage holds `initial_capacity` items, and the false
+        /// positive rate stays below `false_positive_rate`.
+        ///
+        /// # Panics
+        ///
+        /// If `initial_capacity` is zero, or `false_positive_rate` isn't
+        /// strictly between 0 and 1.
+        #[inline]
+        pub fn new(initial_capacity: usize, false_positive_rate: f64) -> GrowableBloomFilter {
+            GrowableBloomFilter::new_with_keys(0, 0, initial_capacity, false_positive_rate)
+        }
+
+        /// Creates an empty filter keyed off the provided keys.
+        ///
+        /// See [`new`](Self::new) for the other arguments.
+        #[inline]
+        pub fn new_with_keys(
+            key0: u64,
+            key1: u64,
+            initial_capacity: usize,
+            false_positive_rate: f64,
+        ) -> GrowableBloomFilter {
+            GrowableBloomFilter::new_with_sip_key(
+                SipKey::from_u64s(key0, key1),
+                initial_capacity,
+                false_positive_rate,
+            )
+        }
+
+        /// Creates an empty filter keyed off the provided [`SipKey`].
+        ///
+        /// See [`new`](Self::new) for the other arguments.
+        pub fn new_with_sip_key(
+            key: SipKey,
+            initial_capacity: usize,
+            false_positive_rate: f64,
+        ) -> GrowableBloomFilter {
+            assert!(
+                initial_capacity > 0,
+                "the initial capacity must not be zero"
+            );
+            assert!(
+                false_positive_rate > 0.0 && false_positive_rate < 1.0,
+                "the false positive rate must be between 0 and 1"
+            );
+
+            // The first stage gets half of the rate, as the rates of all the
+            // stages add up to twice the rate of the first one.
+            let mut initial_k = 1;
+            let mut rate = 0.5;
+            while rate > false_positive_rate / 2.0 {
+                rate /= 2.0;
+                initial_k += 1;
+            }
+            GrowableBloomFilter {
+                key,
+                initial_capacity,
+                initial_k,
+                stages: vec![Stage::new(initial_capacity, initial_k)],
+            }
+        }
+
+        /// Returns the key of the filter.
+        #[inline]
+        pub const fn sip_key(&self) -> SipKey {
+            self.key
+        }
+
+        /// Adds `item` to the filter.
+        ///
+        /// Returns `false`, without inserting it, if the item may already be in
+        /// the filter, and `true` if it certainly wasn't.
+        pub fn insert(&mut self, item: &[u8]) -> bool {
+            let h = SipHasher13::new_with_sip_key(self.key).hash(item);
+            if self
+                .stages
+                .iter()
+                .any(|stage| has_bits(&stage.words, h, stage.k))
+            {
+                return false;
+            }
+
+            let last = self.stages.len() - 1;
+            if self.stages[last].len >= self.stages[last].capacity {
+                let stage = Stage::new(self.stages[last].capacity * 2, self.stages[last].k + 1);
+                self.stages.push(stage);
+            }
+            let stage = self.stages.last_mut().unwrap();
+            set_bits(&mut stage.words, h, stage.k);
+            stage.len += 1;
+            true
+        }
+
+        /// Returns `true` if `item` may be in the filter, and `false` if it
+        /// certainly isn't.
+        pub fn contains(&self, item: &[u8]) -> bool {
+            let h = SipHasher13::new_with_sip_key(self.key).hash(item);
+            self.stages
+                .iter()
+                .any(|stage| has_bits(&stage.words, h, stage.k))
+        }
+
+        /// Adds the items of `other` to the filter.
+        ///
+        /// The stages are merged pairwise, so the false positive rate may end
+        /// up above the target if both filters were nearly full.
+        ///
+        /// # Panics
+        ///
+        /// If the filters don't have the same key, initial capacity and false
+        /// positive rate.
+        pub fn union(&mut self, other: &GrowableBloomFilter) {
+            assert!(
+                same_key(self.key, other.key)
+                    && self.initial_capacity == other.initial_capacity
+                    && self.initial_k == other.initial_k,
+                "Bloom filters with different parameters can't be merged"
+            );
+            for (i, theirs) in other.stages.iter().enumerate() {
+                match self.stages.get_mut(i) {
+                    Some(ours) => {
+                        for (ours, theirs) in ours.words.iter_mut().zip(&theirs.words) {
+                            *ours |= theirs;
+                        }
+                        ours.len = (ours.len + theirs.len).min(ours.capacity);
+                    }
+                    None => self.stages.push(theirs.clone()),
+                }
+            }
+        }
+
+        /// Removes every item from the filter, and frees all the stages but
+        /// the first one.
+        pub fn clear(&mut self) {
+            self.stages.truncate(1);
+            self.stages[0] = Stage::new(self.initial_capacity, self.initial_k);
+        }
+
+        /// Returns the number of items inserted.
+        ///
+        /// Items reported as possibly present by [`insert`](Self::insert) are
+        /// not counted.
+        pub fn len(&self) -> usize {
+            self.stages.iter().map(|stage| stage.len).sum()
+        }
+
+        /// Returns `true` if no item was inserted.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the number of stages.
+        #[inline]
+        pub fn stages(&self) -> usize {
+            self.stages.len()
+        }
+
+        /// Returns the number of bits of all the stages.
+        pub fn bits(&self) -> usize {
+            self.stages.iter().map(|stage| stage.words.len() * 64).sum()
+        }
+    }
+
+    impl PartialEq for GrowableBloomFilter {
+        fn eq(&self, other: &GrowableBloomFilter) -> bool {
+            same_key(self.key, other.key)
+                && self.initial_capacity == other.initial_capacity
+                && self.initial_k == other.initial_k
+                && self.stages == other.stages
+        }
+    }
+
+    impl Eq for GrowableBloomFilter {}
+
+    impl fmt::Debug for GrowableBloomFilter {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("GrowableBloomFilter")
+                .field("len", &self.len())
+                .field("stages", &self.stages())
+                .field("bits", &self.bits())
+                .finish_non_exhaustive()
+        }
+    }
+}
@@ -0,0 +1,714 @@
+// Copyright 2012-2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An implementation of HalfSipHash with a 32-bit output.
+//!
+//! HalfSipHash works on 32-bit words and takes a 64-bit key, which makes it
+//! considerably faster than SipHash on 32-bit and smaller targets. It offers
+//! a lower security margin than SipHash and should only be used where the
+//! 64-bit ARX rounds are too expensive.
+
+use core::fmt;
+use core::hash;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr;
+
+use crate::{Sip, Sip13Rounds, Sip24Rounds};
+
+/// An implementation of HalfSipHash 1-3.
+///
+/// See: <https://github.com/veorq/SipHash>
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipHasher13 {
+    hasher: Hasher<Sip13Rounds>,
+}
+
+/// An implementation of HalfSipHash 2-4.
+///
+/// See: <https://github.com/veorq/SipHash>
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipHasher24 {
+    hasher: Hasher<Sip24Rounds>,
+}
+
+/// An implementation of HalfSipHash 2-4.
+///
+/// See: <https://github.com/veorq/SipHash>
+///
+/// HalfSipHash is a variant of SipHash operating on 32-bit words, intended
+/// for hash tables on 32-bit and embedded targets. Its security level is
+/// lower than SipHash's and all cryptographic uses of this implementation
+/// are _strongly discouraged_.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipHasher(SipHasher24);
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Hasher<S: Sip> {
+    k0: u32,
+    k1: u32,
+    length: usize, // how many bytes we've processed
+    state: State,  // hash State
+    tail: u32,     // unprocessed bytes le
+    ntail: usize,  // how many bytes in tail are valid
+    _marker: PhantomData<S>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct State {
+    v0: u32,
+    v2: u32,
+    v1: u32,
+    v3: u32,
+}
+
+macro_rules! compress {
+    ($state:expr) => {{
+        compress!($state.v0, $state.v1, $state.v2, $state.v3)
+    }};
+    ($v0:expr, $v1:expr, $v2:expr, $v3:expr) => {{
+        $v0 = $v0.wrapping_add($v1);
+        $v1 = $v1.rotate_left(5);
+        $v1 ^= $v0;
+        $v0 = $v0.rotate_left(16);
+        $v2 = $v2.wrapping_add($v3);
+        $v3 = $v3.rotate_left(8);
+        $v3 ^= $v2;
+        $v0 = $v0.wrapping_add($v3);
+        $v3 = $v3.rotate_left(7);
+        $v3 ^= $v0;
+        $v2 = $v2.wrapping_add($v1);
+        $v1 = $v1.rotate_left(13);
+        $v1 ^= $v2;
+        $v2 = $v2.rotate_left(16);
+    }};
+}
+
+/// Loads an integer of the desired type from a byte stream, in LE order. Uses
+/// `copy_nonoverlapping` to let the compiler generate the most efficient way
+/// to load it from a possibly unaligned address.
+///
+/// Unsafe because: unchecked indexing at `i..i+size_of(int_ty)`
+macro_rules! load_int_le {
+    ($buf:expr, $i:expr, $int_ty:ident) => {{
+        debug_assert!($i + mem::size_of::<$int_ty>() <= $buf.len());
+        let mut data = 0 as $int_ty;
+        ptr::copy_nonoverlapping(
+            $buf.as_ptr().add($i),
+            &mut data as *mut _ as *mut u8,
+            mem::size_of::<$int_ty>(),
+        );
+        data.to_le()
+    }};
+}
+
+/// Loads a u32 using up to 3 bytes of a byte slice.
+///
+/// Unsafe because: unchecked indexing at start..start+len
+#[inline]
+const unsafe fn u8to32_le(buf: &[u8], start: usize, len: usize) -> u32 {
+    debug_assert!(len < 4);
+    let mut i = 0; // current byte index (from LSB) in the output u32
+    let mut out = 0;
+    if i + 1 < len {
+        out = load_int_le!(buf, start + i, u16) as u32;
+        i += 2
+    }
+    if i < len {
+        out |= (ptr::read(buf.as_ptr().add(start + i)) as u32) << (i * 8);
+        i += 1;
+    }
+    debug_assert!(i == len);
+    out
+}
+
+/// Converts an 8 byte key into the two 32-bit key words.
+#[inline]
+const fn keys_from_bytes(key: &[u8; 8]) -> (u32, u32) {
+    (
+        u32::from_le_bytes([key[0], key[1], key[2], key[3]]),
+        u32::from_le_bytes([key[4], key[5], key[6], key[7]]),
+    )
+}
+
+/// Converts the two 32-bit key words into an 8 byte key.
+#[inline]
+const fn keys_to_bytes(key0: u32, key1: u32) -> [u8; 8] {
+    let b0 = key0.to_le_bytes();
+    let b1 = key1.to_le_bytes();
+    [b0[0], b0[1], b0[2], b0[3], b1[0], b1[1], b1[2], b1[3]]
+}
+
+impl SipHasher {
+    /// Creates a new `SipHasher` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> SipHasher {
+        SipHasher::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipHasher` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u32, key1: u32) -> SipHasher {
+        SipHasher(SipHasher24::new_with_keys(key0, key1))
+    }
+
+    /// Creates a `SipHasher` from an 8 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 8]) -> SipHasher {
+        let (key0, key1) = keys_from_bytes(key);
+        Self::new_with_keys(key0, key1)
+    }
+
+    /// Get the keys used by this hasher
+    pub const fn keys(&self) -> (u32, u32) {
+        (self.0.hasher.k0, self.0.hasher.k1)
+    }
+
+    /// Get the key used by this hasher as an 8 byte vector
+    pub const fn key(&self) -> [u8; 8] {
+        keys_to_bytes(self.0.hasher.k0, self.0.hasher.k1)
+    }
+
+    /// Hash a byte array - This is the easiest and safest way to use HalfSipHash.
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> u32 {
+        let mut hasher = self.0.hasher;
+        hasher.write(bytes);
+        hasher.finish32()
+    }
+}
+
+impl SipHasher13 {
+    /// Creates a new `SipHasher13` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> SipHasher13 {
+        SipHasher13::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipHasher13` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u32, key1: u32) -> SipHasher13 {
+        SipHasher13 {
+            hasher: Hasher::new_with_keys(key0, key1),
+        }
+    }
+
+    /// Creates a `SipHasher13` from an 8 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 8]) -> SipHasher13 {
+        let (key0, key1) = keys_from_bytes(key);
+        Self::new_with_keys(key0, key1)
+    }
+
+    /// Get the keys used by this hasher
+    pub const fn keys(&self) -> (u32, u32) {
+        (self.hasher.k0, self.hasher.k1)
+    }
+
+    /// Get the key used by this hasher as an 8 byte vector
+    pub const fn key(&self) -> [u8; 8] {
+        keys_to_bytes(self.hasher.k0, self.hasher.k1)
+    }
+
+    /// Hash a byte array - This is the easiest and safest way to use HalfSipHash.
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> u32 {
+        let mut hasher = self.hasher;
+        hasher.write(bytes);
+        hasher.finish32()
+    }
+}
+
+impl SipHasher24 {
+    /// Creates a new `SipHasher24` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> SipHasher24 {
+        SipHasher24::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipHasher24` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u32, key1: u32) -> SipHasher24 {
+        SipHasher24 {
+            hasher: Hasher::new_with_keys(key0, key1),
+        }
+    }
+
+    /// Creates a `SipHasher24` from an 8 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 8]) -> SipHasher24 {
+        let (key0, key1) = keys_from_bytes(key);
+        Self::new_with_keys(key0, key1)
+    }
+
+    /// Get the keys used by this hasher
+    pub const fn keys(&self) -> (u32, u32) {
+        (self.hasher.k0, self.hasher.k1)
+    }
+
+    /// Get the key used by this hasher as an 8 byte vector
+    pub const fn key(&self) -> [u8; 8] {
+        keys_to_bytes(self.hasher.k0, self.hasher.k1)
+    }
+
+    /// Hash a byte array - This is the easiest and safest way to use HalfSipHash.
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> u32 {
+        let mut hasher = self.hasher;
+        hasher.write(bytes);
+        hasher.finish32()
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    #[inline]
+    const fn new_with_keys(key0: u32, key1: u32) -> Hasher<S> {
+        let mut state = Hasher {
+            k0: key0,
+            k1: key1,
+            length: 0,
+            state: State {
+                v0: 0,
+                v1: 0,
+                v2: 0,
+                v3: 0,
+            },
+            tail: 0,
+            ntail: 0,
+            _marker: PhantomData,
+        };
+        state = state.reset();
+        state
+    }
+
+    #[inline]
+    #[must_use]
+    const fn reset(mut self) -> Self {
+        self.length = 0;
+        self.state.v0 = self.k0;
+        self.state.v1 = self.k1;
+        self.state.v2 = self.k0 ^ 0x6c796765;
+        self.state.v3 = self.k1 ^ 0x74656462;
+        self.ntail = 0;
+        self
+    }
+
+    // A specialized write function for values with size <= 4.
+    //
+    // The hashing of multi-byte integers depends on endianness. E.g.:
+    // - little-endian: `write_u32(0xDDCCBBAA)` == `write([0xAA, 0xBB, 0xCC, 0xDD])`
+    // - big-endian:    `write_u32(0xDDCCBBAA)` == `write([0xDD, 0xCC, 0xBB, 0xAA])`
+    //
+    // This function does the right thing for little-endian hardware. On
+    // big-endian hardware `x` must be byte-swapped first to give the right
+    // behaviour. After any byte-swapping, the input must be zero-extended to
+    // 32-bits. The caller is responsible for the byte-swapping and
+    // zero-extension.
+    #[inline]
+    const fn short_write<T>(&mut self, _x: &T, x: u32) {
+        let size = mem::size_of::<T>();
+        self.length += size;
+
+        // The original number must be zero-extended, not sign-extended.
+        debug_assert!(if size < 4 { x >> (8 * size) == 0 } else { true });
+
+        // The number of bytes needed to fill `self.tail`.
+        let needed = 4 - self.ntail;
+
+        self.tail |= x << (8 * self.ntail);
+        if size < needed {
+            self.ntail += size;
+            return;
+        }
+
+        // `self.tail` is full, process it.
+        self.state.v3 ^= self.tail;
+        self.state = c_rounds::<S>(self.state);
+        self.state.v0 ^= self.tail;
+
+        self.ntail = size - needed;
+        self.tail = if needed < 4 { x >> (8 * needed) } else { 0 };
+    }
+}
+
+impl hash::Hasher for SipHasher {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write_u64(i);
+    }
+}
+
+impl SipHasher {
+    #[inline]
+    pub const fn write(&mut self, msg: &[u8]) {
+        self.0.write(msg)
+    }
+
+    /// Return the 32-bit hash, zero-extended to a `u64`.
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    /// Return the 32-bit hash.
+    #[inline]
+    pub const fn finish32(&self) -> u32 {
+        self.0.finish32()
+    }
+
+    #[inline]
+    pub const fn write_usize(&mut self, i: usize) {
+        self.0.write_usize(i);
+    }
+
+    #[inline]
+    pub const fn write_u8(&mut self, i: u8) {
+        self.0.write_u8(i);
+    }
+
+    #[inline]
+    pub const fn write_u16(&mut self, i: u16) {
+        self.0.write_u16(i);
+    }
+
+    #[inline]
+    pub const fn write_u32(&mut self, i: u32) {
+        self.0.write_u32(i);
+    }
+
+    #[inline]
+    pub const fn write_u64(&mut self, i: u64) {
+        self.0.write_u64(i);
+    }
+}
+
+impl hash::Hasher for SipHasher13 {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write_u64(i);
+    }
+}
+
+impl SipHasher13 {
+    #[inline]
+    pub const fn write(&mut self, msg: &[u8]) {
+        self.hasher.write(msg)
+    }
+
+    /// Return the 32-bit hash, zero-extended to a `u64`.
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    /// Return the 32-bit hash.
+    #[inline]
+    pub const fn finish32(&self) -> u32 {
+        self.hasher.finish32()
+    }
+
+    #[inline]
+    pub const fn write_usize(&mut self, i: usize) {
+        self.hasher.write_usize(i);
+    }
+
+    #[inline]
+    pub const fn write_u8(&mut self, i: u8) {
+        self.hasher.write_u8(i);
+    }
+
+    #[inline]
+    pub const fn write_u16(&mut self, i: u16) {
+        self.hasher.write_u16(i);
+    }
+
+    #[inline]
+    pub const fn write_u32(&mut self, i: u32) {
+        self.hasher.write_u32(i);
+    }
+
+    #[inline]
+    pub const fn write_u64(&mut self, i: u64) {
+        self.hasher.write_u64(i);
+    }
+}
+
+impl hash::Hasher for SipHasher24 {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write_u64(i);
+    }
+}
+
+impl SipHasher24 {
+    #[inline]
+    pub const fn write(&mut self, msg: &[u8]) {
+        self.hasher.write(msg)
+    }
+
+    /// Return the 32-bit hash, zero-extended to a `u64`.
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    /// Return the 32-bit hash.
+    #[inline]
+    pub const fn finish32(&self) -> u32 {
+        self.hasher.finish32()
+    }
+
+    #[inline]
+    pub const fn write_usize(&mut self, i: usize) {
+        self.hasher.write_usize(i);
+    }
+
+    #[inline]
+    pub const fn write_u8(&mut self, i: u8) {
+        self.hasher.write_u8(i);
+    }
+
+    #[inline]
+    pub const fn write_u16(&mut self, i: u16) {
+        self.hasher.write_u16(i);
+    }
+
+    #[inline]
+    pub const fn write_u32(&mut self, i: u32) {
+        self.hasher.write_u32(i);
+    }
+
+    #[inline]
+    pub const fn write_u64(&mut self, i: u64) {
+        self.hasher.write_u64(i);
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    // Values wider than a word are fed through `write` in native byte order,
+    // which is what `short_write` would do on a 64-bit word hasher.
+    #[inline]
+    const fn write_usize(&mut self, i: usize) {
+        self.write(&i.to_ne_bytes());
+    }
+
+    #[inline]
+    const fn write_u8(&mut self, i: u8) {
+        self.short_write(&i, i as u32);
+    }
+
+    #[inline]
+    const fn write_u16(&mut self, i: u16) {
+        self.short_write(&i, i.to_le() as u32);
+    }
+
+    #[inline]
+    const fn write_u32(&mut self, i: u32) {
+        self.short_write(&i, i.to_le());
+    }
+
+    #[inline]
+    const fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_ne_bytes());
+    }
+
+    #[inline]
+    const fn write(&mut self, msg: &[u8]) {
+        let length = msg.len();
+        self.length += length;
+
+        let mut needed = 0;
+
+        if self.ntail != 0 {
+            needed = 4 - self.ntail;
+            if length < needed {
+                self.tail |= unsafe { u8to32_le(msg, 0, length) } << (8 * self.ntail);
+                self.ntail += length;
+                return;
+            } else {
+                self.tail |= unsafe { u8to32_le(msg, 0, needed) } << (8 * self.ntail);
+                self.state.v3 ^= self.tail;
+                self.state = c_rounds::<S>(self.state);
+                self.state.v0 ^= self.tail;
+                self.ntail = 0;
+            }
+        }
+
+        // Buffered tail is now flushed, process new input.
+        let len = length - needed;
+        let left = len & 0x3;
+
+        let mut i = needed;
+        while i < len - left {
+            let mi = unsafe { load_int_le!(msg, i, u32) };
+
+            self.state.v3 ^= mi;
+            self.state = c_rounds::<S>(self.state);
+            self.state.v0 ^= mi;
+
+            i += 4;
+        }
+
+        self.tail = unsafe { u8to32_le(msg, i, left) };
+        self.ntail = left;
+    }
+
+    #[inline]
+    const fn finish32(&self) -> u32 {
+        let mut state = self.state;
+
+        let b: u32 = ((self.length as u32 & 0xff) << 24) | self.tail;
+
+        state.v3 ^= b;
+        state = c_rounds::<S>(state);
+        state.v0 ^= b;
+
+        state.v2 ^= 0xff;
+        state = d_rounds::<S>(state);
+
+        state.v1 ^ state.v3
+    }
+
+    #[inline]
+    const fn finish(&self) -> u64 {
+        self.finish32() as u64
+    }
+}
+
+impl<S: Sip> fmt::Debug for Hasher<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The keys, the state derived from them and the buffered message
+        // bytes are not printed.
+        f.debug_struct("Hasher")
+            .field("length", &self.length)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Sip> Default for Hasher<S> {
+    /// Creates a `Hasher<S>` with the two initial keys set to 0.
+    #[inline]
+    fn default() -> Hasher<S> {
+        Hasher::new_with_keys(0, 0)
+    }
+}
+
+const fn c_rounds<S: Sip>(mut state: State) -> State {
+    let mut i = 0;
+    while i < S::C_ROUNDS {
+        compress!(state);
+        i += 1;
+    }
+    state
+}
+
+const fn d_rounds<S: Sip>(mut state: State) -> State {
+    let mut i = 0;
+    while i < S::D_ROUNDS {
+        compress!(state);
+        i += 1;
+    }
+    state
+}
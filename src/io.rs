@@ -0,0 +1,140 @@
+//! Adapters hashing data as it is read or written.
+//!
+//! ```rust
+//! use std::io;
+//!
+//! use const_siphasher::io::HashingReader;
+//! use const_siphasher::sip::SipHasher24;
+//!
+//! let data: &[u8] = b"some file contents";
+//! let mut reader = HashingReader::new(data, SipHasher24::new_with_keys(1, 2));
+//! io::copy(&mut reader, &mut io::sink()).unwrap();
+//! assert_eq!(
+//!     reader.hasher().finish(),
+//!     SipHasher24::new_with_keys(1, 2).hash(data)
+//! );
+//! ```
+
+use core::hash;
+use std::io;
+
+/// A reader feeding every byte it reads from `R` into a hasher.
+///
+/// Only the bytes actually returned by the inner reader are hashed, so the
+/// hash covers exactly the data that went through the adapter.
+#[derive(Debug, Clone, Default)]
+pub struct HashingReader<R, H> {
+    inner: R,
+    hasher: H,
+}
+
+impl<R, H> HashingReader<R, H> {
+    /// Creates an adapter reading from `inner` and hashing with `hasher`.
+    #[inline]
+    pub const fn new(inner: R, hasher: H) -> Self {
+        HashingReader { inner, hasher }
+    }
+
+    /// Returns the hasher, which has seen all the data read so far.
+    #[inline]
+    pub const fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns a mutable reference to the hasher.
+    #[inline]
+    pub fn hasher_mut(&mut self) -> &mut H {
+        &mut self.hasher
+    }
+
+    /// Returns a reference to the inner reader.
+    #[inline]
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// Data read directly from it isn't hashed.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader and the hasher.
+    #[inline]
+    pub fn into_parts(self) -> (R, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<R: io::Read, H: hash::Hasher> io::Read for HashingReader<R, H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.write(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// A writer feeding every byte it writes to `W` into a hasher.
+///
+/// Only the bytes accepted by the inner writer are hashed, so the hash
+/// covers exactly the data that went through the adapter.
+#[derive(Debug, Clone, Default)]
+pub struct HashingWriter<W, H> {
+    inner: W,
+    hasher: H,
+}
+
+impl<W, H> HashingWriter<W, H> {
+    /// Creates an adapter writing to `inner` and hashing with `hasher`.
+    #[inline]
+    pub const fn new(inner: W, hasher: H) -> Self {
+        HashingWriter { inner, hasher }
+    }
+
+    /// Returns the hasher, which has seen all the data written so far.
+    #[inline]
+    pub const fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns a mutable reference to the hasher.
+    #[inline]
+    pub fn hasher_mut(&mut self) -> &mut H {
+        &mut self.hasher
+    }
+
+    /// Returns a reference to the inner writer.
+    #[inline]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Data written directly to it isn't hashed.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer and the hasher.
+    #[inline]
+    pub fn into_parts(self) -> (W, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<W: io::Write, H: hash::Hasher> io::Write for HashingWriter<W, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.write(&buf[..n]);
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
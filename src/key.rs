@@ -0,0 +1,223 @@
+//! The key type shared by the `sip` and `sip128` hashers.
+
+use core::fmt;
+
+/// A 128-bit SipHash key, made of the two 64-bit words `k0` and `k1`.
+///
+/// The byte form of the key is the little-endian encoding of `k0` followed by
+/// the little-endian encoding of `k1`, as in the reference implementation.
+///
+/// The `Debug` implementation does not print the key material.
+///
+/// Every hasher has a `new_with_sip_key` constructor next to `new_with_keys`
+/// and `new_with_key`. The constructors are `const fn`s, which can't call
+/// trait methods, so they can't take an `impl Into<SipKey>`; the `From`
+/// implementations are there for non-`const` code.
+///
+/// # Zeroize
+///
+/// With the `zeroize` feature, `SipKey` and all the `sip` and `sip128`
+/// hashers implement [`Zeroize`](zeroize::Zeroize), but they are not wiped
+/// on drop themselves: a type that implements `Drop` can't be `Copy`, and
+/// they must stay `Copy` to be used by value in `const fn`s. Keep the key in
+/// a [`SecretSipKey`] instead, which is wiped on drop and hands out hashers
+/// wrapped in [`Zeroizing`](zeroize::Zeroizing), wiped on drop as well.
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipKey {
+    k0: u64,
+    k1: u64,
+}
+
+impl SipKey {
+    /// Creates a key from its two 64-bit words.
+    #[inline]
+    pub const fn from_u64s(key0: u64, key1: u64) -> SipKey {
+        SipKey { k0: key0, k1: key1 }
+    }
+
+    /// Returns the two 64-bit words of this key.
+    #[inline]
+    pub const fn to_u64s(&self) -> (u64, u64) {
+        (self.k0, self.k1)
+    }
+
+    /// Creates a key from a 16 byte vector.
+    #[inline]
+    pub const fn from_bytes(key: [u8; 16]) -> SipKey {
+        SipKey::from_key_bytes(&key)
+    }
+
+    /// Creates a key from a reference to a 16 byte vector.
+    #[inline]
+    pub const fn from_key_bytes(key: &[u8; 16]) -> SipKey {
+        let k = key;
+        SipKey {
+            k0: u64::from_le_bytes([k[0], k[1], k[2], k[3], k[4], k[5], k[6], k[7]]),
+            k1: u64::from_le_bytes([k[8], k[9], k[10], k[11], k[12], k[13], k[14], k[15]]),
+        }
+    }
+
+    /// Returns this key as a 16 byte vector.
+    #[inline]
+    pub const fn to_key_bytes(&self) -> [u8; 16] {
+        let b0 = self.k0.to_le_bytes();
+        let b1 = self.k1.to_le_bytes();
+        [
+            b0[0], b0[1], b0[2], b0[3], b0[4], b0[5], b0[6], b0[7], b1[0], b1[1], b1[2], b1[3],
+            b1[4], b1[5], b1[6], b1[7],
+        ]
+    }
+
+    /// Creates a random key.
+    ///
+    /// The key is drawn from the standard library's `RandomState`, which is
+    /// seeded from the operating system's random number generator.
+    #[cfg(feature = "std")]
+    pub fn random() -> SipKey {
+        use core::hash::{BuildHasher, Hasher};
+        use std::collections::hash_map::RandomState;
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u8(0);
+        let key0 = hasher.finish();
+        hasher.write_u8(1);
+        let key1 = hasher.finish();
+        SipKey::from_u64s(key0, key1)
+    }
+}
+
+impl From<[u8; 16]> for SipKey {
+    #[inline]
+    fn from(key: [u8; 16]) -> SipKey {
+        SipKey::from_bytes(key)
+    }
+}
+
+impl From<&[u8; 16]> for SipKey {
+    #[inline]
+    fn from(key: &[u8; 16]) -> SipKey {
+        SipKey::from_key_bytes(key)
+    }
+}
+
+impl From<(u64, u64)> for SipKey {
+    #[inline]
+    fn from((key0, key1): (u64, u64)) -> SipKey {
+        SipKey::from_u64s(key0, key1)
+    }
+}
+
+impl fmt::Debug for SipKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SipKey").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipKey {
+    fn zeroize(&mut self) {
+        self.k0.zeroize();
+        self.k1.zeroize();
+    }
+}
+
+/// A [`SipKey`] wiped from memory on drop, with the `zeroize` feature.
+///
+/// Unlike `SipKey`, it is not `Copy`, so the key is only duplicated by
+/// explicit clones, each of them wiped on drop. The hashers it creates are
+/// wrapped in [`Zeroizing`](zeroize::Zeroizing), and wiped on drop too.
+///
+/// ```rust
+/// use const_siphasher::SecretSipKey;
+///
+/// let key = SecretSipKey::from_u64s(1, 2);
+/// let mut hasher = key.sip_hasher13();
+/// hasher.write(b"foo");
+/// let h = hasher.finish();
+/// // both `hasher` and `key` are wiped here
+/// ```
+#[cfg(feature = "zeroize")]
+#[derive(Clone, Default)]
+pub struct SecretSipKey(SipKey);
+
+#[cfg(feature = "zeroize")]
+impl SecretSipKey {
+    /// Creates a key from its two 64-bit words.
+    #[inline]
+    pub const fn from_u64s(key0: u64, key1: u64) -> SecretSipKey {
+        SecretSipKey(SipKey::from_u64s(key0, key1))
+    }
+
+    /// Creates a key from a reference to a 16 byte vector.
+    #[inline]
+    pub const fn from_key_bytes(key: &[u8; 16]) -> SecretSipKey {
+        SecretSipKey(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a random key, as [`SipKey::random`].
+    #[cfg(feature = "std")]
+    pub fn random() -> SecretSipKey {
+        SecretSipKey(SipKey::random())
+    }
+
+    /// Returns the key.
+    ///
+    /// The key is `Copy`, and copies of it are not wiped on drop.
+    #[inline]
+    pub const fn expose(&self) -> &SipKey {
+        &self.0
+    }
+
+    /// Returns a SipHash-1-3 hasher with this key, wiped on drop.
+    pub fn sip_hasher13(&self) -> zeroize::Zeroizing<crate::sip::SipHasher13> {
+        zeroize::Zeroizing::new(crate::sip::SipHasher13::new_with_sip_key(self.0))
+    }
+
+    /// Returns a SipHash-2-4 hasher with this key, wiped on drop.
+    pub fn sip_hasher24(&self) -> zeroize::Zeroizing<crate::sip::SipHasher24> {
+        zeroize::Zeroizing::new(crate::sip::SipHasher24::new_with_sip_key(self.0))
+    }
+
+    /// Returns a 128-bit SipHash-1-3 hasher with this key, wiped on drop.
+    pub fn sip128_hasher13(&self) -> zeroize::Zeroizing<crate::sip128::SipHasher13> {
+        zeroize::Zeroizing::new(crate::sip128::SipHasher13::new_with_sip_key(self.0))
+    }
+
+    /// Returns a 128-bit SipHash-2-4 hasher with this key, wiped on drop.
+    pub fn sip128_hasher24(&self) -> zeroize::Zeroizing<crate::sip128::SipHasher24> {
+        zeroize::Zeroizing::new(crate::sip128::SipHasher24::new_with_sip_key(self.0))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl From<SipKey> for SecretSipKey {
+    #[inline]
+    fn from(key: SipKey) -> SecretSipKey {
+        SecretSipKey(key)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl fmt::Debug for SecretSipKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretSipKey").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretSipKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretSipKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretSipKey {}
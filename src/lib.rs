@@ -4,6 +4,25 @@
 #![allow(clippy::cast_lossless)]
 #![allow(clippy::many_single_char_names)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod key;
+mod macros;
+mod many;
+mod value;
+
+pub mod bloom;
+pub mod halfsip;
+pub mod halfsip64;
+#[cfg(feature = "std")]
+pub mod hll;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod phf;
+pub mod sharding;
 pub mod sip;
 pub mod sip128;
 
@@ -13,6 +32,35 @@ mod tests;
 #[cfg(test)]
 mod tests128;
 
+#[cfg(test)]
+mod tests_bloom;
+
+#[cfg(test)]
+mod tests_halfsip;
+
+#[cfg(test)]
+mod tests_halfsip64;
+
+#[cfg(all(test, feature = "std"))]
+mod tests_hll;
+
+#[cfg(test)]
+mod tests_phf;
+
+#[cfg(test)]
+mod tests_sharding;
+
+#[cfg(feature = "zeroize")]
+pub use key::SecretSipKey;
+pub use key::SipKey;
+pub use value::{ConstHash, HashValue};
+
+#[cfg(feature = "derive")]
+pub use const_siphasher_derive::PortableSipHash;
+
+#[doc(hidden)]
+pub use macros::{__Bytes, __Key};
+
 #[doc(hidden)]
 trait Sip {
     const C_ROUNDS: usize;
@@ -35,6 +83,39 @@ impl Sip for Sip24Rounds {
     const D_ROUNDS: usize = 4;
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+struct SipCDRounds<const C: usize, const D: usize>;
+
+impl<const C: usize, const D: usize> Sip for SipCDRounds<C, D> {
+    const C_ROUNDS: usize = C;
+    const D_ROUNDS: usize = D;
+}
+
+/// Returns the OR of the XOR of every pair of bytes of `a` and `b`, which is
+/// zero only if they are equal.
+///
+/// Every byte is always visited, so the time taken doesn't depend on where
+/// (or whether) the arrays differ. This is best effort: the accumulator goes
+/// through [`black_box`](core::hint::black_box) after every byte, so the
+/// optimizer can't tell when it is saturated and turn the loop into an early
+/// exit, but Rust makes no guarantee about the generated code.
+#[inline]
+const fn ct_diff<const N: usize>(a: &[u8; N], b: &[u8; N]) -> u8 {
+    let mut diff = 0;
+    let mut i = 0;
+    while i < N {
+        diff = core::hint::black_box(diff | (a[i] ^ b[i]));
+        i += 1;
+    }
+    diff
+}
+
+/// Compares two byte arrays in constant time.
+#[inline]
+const fn ct_eq<const N: usize>(a: &[u8; N], b: &[u8; N]) -> bool {
+    ct_diff(a, b) == 0
+}
+
 #[cfg(any(feature = "serde", feature = "serde_std", feature = "serde_no_std"))]
 pub mod reexports {
     pub use serde;
@@ -47,5 +128,7 @@ pub mod prelude {
 
     pub use sip128::Hasher128 as _;
 
-    pub use crate::{sip, sip128};
+    pub use crate::ConstHash as _;
+
+    pub use crate::{halfsip, halfsip64, sip, sip128};
 }
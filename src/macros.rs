@@ -0,0 +1,130 @@
+//! Macros for hashing literals at compile time.
+
+use crate::SipKey;
+
+/// Hashes a sequence of string or byte literals with SipHash 1-3, producing a
+/// `u64`.
+///
+/// The first argument is the key, as a `(u64, u64)` pair, a `&[u8; 16]` or a
+/// [`SipKey`](crate::SipKey). The remaining arguments are `&str`, `&[u8]` or
+/// `&[u8; N]` values (including the output of `include_bytes!`), which are
+/// hashed as if they had been concatenated.
+///
+/// The expansion only calls `const fn`s, so it can be used to initialize
+/// `const` items, which can in turn be used as `match` patterns.
+///
+/// ```rust
+/// use const_siphasher::siphash13;
+///
+/// const FOO: u64 = siphash13!((1, 2), "foo");
+/// const FOOBAR: u64 = siphash13!((1, 2), "foo", b"bar");
+///
+/// match siphash13!((1, 2), "foobar") {
+///     FOO => unreachable!(),
+///     FOOBAR => {}
+///     _ => unreachable!(),
+/// }
+/// ```
+#[macro_export]
+macro_rules! siphash13 {
+    ($key:expr $(, $data:expr)* $(,)?) => {
+        $crate::__siphash!($crate::sip::SipHasher13, finish, $key $(, $data)*)
+    };
+}
+
+/// Hashes a sequence of string or byte literals with SipHash 2-4, producing a
+/// `u64`.
+///
+/// See [`siphash13!`] for the accepted arguments.
+#[macro_export]
+macro_rules! siphash24 {
+    ($key:expr $(, $data:expr)* $(,)?) => {
+        $crate::__siphash!($crate::sip::SipHasher24, finish, $key $(, $data)*)
+    };
+}
+
+/// Hashes a sequence of string or byte literals with SipHash128 1-3,
+/// producing a [`Hash128`](crate::sip128::Hash128).
+///
+/// See [`siphash13!`] for the accepted arguments.
+#[macro_export]
+macro_rules! siphash13_128 {
+    ($key:expr $(, $data:expr)* $(,)?) => {
+        $crate::__siphash!($crate::sip128::SipHasher13, finish128, $key $(, $data)*)
+    };
+}
+
+/// Hashes a sequence of string or byte literals with SipHash128 2-4,
+/// producing a [`Hash128`](crate::sip128::Hash128).
+///
+/// See [`siphash13!`] for the accepted arguments.
+#[macro_export]
+macro_rules! siphash24_128 {
+    ($key:expr $(, $data:expr)* $(,)?) => {
+        $crate::__siphash!($crate::sip128::SipHasher24, finish128, $key $(, $data)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __siphash {
+    ($hasher:path, $finish:ident, $key:expr $(, $data:expr)*) => {{
+        let (key0, key1) = $crate::__Key($key).keys();
+        #[allow(unused_mut)]
+        let mut hasher = <$hasher>::new_with_keys(key0, key1);
+        $(hasher.write($crate::__Bytes($data).as_bytes());)*
+        hasher.$finish()
+    }};
+}
+
+/// Wraps a macro argument so that `&str`, `&[u8]` and `&[u8; N]` can all be
+/// turned into bytes by a `const fn`.
+#[doc(hidden)]
+pub struct __Bytes<T>(pub T);
+
+impl<'a> __Bytes<&'a str> {
+    #[inline]
+    pub const fn as_bytes(self) -> &'a [u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl<'a> __Bytes<&'a [u8]> {
+    #[inline]
+    pub const fn as_bytes(self) -> &'a [u8] {
+        self.0
+    }
+}
+
+impl<'a, const N: usize> __Bytes<&'a [u8; N]> {
+    #[inline]
+    pub const fn as_bytes(self) -> &'a [u8] {
+        self.0
+    }
+}
+
+/// Wraps a macro key argument so that `(u64, u64)`, `&[u8; 16]` and
+/// [`SipKey`] can all be turned into a pair of keys by a `const fn`.
+#[doc(hidden)]
+pub struct __Key<T>(pub T);
+
+impl __Key<(u64, u64)> {
+    #[inline]
+    pub const fn keys(self) -> (u64, u64) {
+        self.0
+    }
+}
+
+impl __Key<&[u8; 16]> {
+    #[inline]
+    pub const fn keys(self) -> (u64, u64) {
+        SipKey::from_key_bytes(self.0).to_u64s()
+    }
+}
+
+impl __Key<SipKey> {
+    #[inline]
+    pub const fn keys(self) -> (u64, u64) {
+        self.0.to_u64s()
+    }
+}
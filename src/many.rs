@@ -0,0 +1,614 @@
+//! Hashing of many messages with the same key.
+//!
+//! Messages are hashed in groups, each message of a group in its own lane, so
+//! that the independent SipHash pipelines can run side by side: interleaved in
+//! scalar code, or in the lanes of SIMD vectors. Every lane compresses one
+//! block per step, and the lanes whose message has no block left keep their
+//! state. Once every message of a group has been compressed, all the lanes are
+//! finalized together.
+//!
+//! The functions here work on the raw `v0..v3` state, and return both halves
+//! of the output: only the first one is used in 64-bit mode.
+
+use crate::Sip;
+
+/// The largest number of lanes of any backend.
+pub(crate) const MAX_LANES: usize = 4;
+
+/// The number of messages the scalar code hashes side by side.
+const SCALAR_LANES: usize = 4;
+
+/// The implementations of the lanes available on this CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Backend {
+    Scalar,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Sse2,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx2,
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    Neon,
+}
+
+impl Backend {
+    /// Returns the fastest backend supported by the CPU, detected at runtime
+    /// with `std`, and from the enabled target features otherwise.
+    pub(crate) fn detect() -> Backend {
+        #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            if std::is_x86_feature_detected!("avx2") {
+                return Backend::Avx2;
+            }
+            if std::is_x86_feature_detected!("sse2") {
+                return Backend::Sse2;
+            }
+        }
+        #[cfg(all(not(feature = "std"), any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            if cfg!(target_feature = "avx2") {
+                return Backend::Avx2;
+            }
+            if cfg!(target_feature = "sse2") {
+                return Backend::Sse2;
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            return Backend::Neon;
+        }
+        #[allow(unreachable_code)]
+        Backend::Scalar
+    }
+
+    /// Returns the number of messages hashed at once.
+    pub(crate) const fn lanes(self) -> usize {
+        match self {
+            Backend::Scalar => SCALAR_LANES,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse2 => 2,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => 4,
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            Backend::Neon => 2,
+        }
+    }
+
+    /// Hashes up to [`lanes`](Self::lanes) messages, starting from `init`
+    /// after `length` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If there are more messages than lanes.
+    #[inline]
+    pub(crate) fn hash_group<S: Sip, const WIDE: bool>(
+        self,
+        init: [u64; 4],
+        length: usize,
+        msgs: &[&[u8]],
+    ) -> [[u64; 2]; MAX_LANES] {
+        assert!(msgs.len() <= self.lanes());
+        match self {
+            Backend::Scalar => hash_group::<S, WIDE>(init, length, msgs),
+            // SAFETY: the backends are only used when detected.
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse2 => unsafe { x86::hash_group_sse2::<S, WIDE>(init, length, msgs) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => unsafe { x86::hash_group_avx2::<S, WIDE>(init, length, msgs) },
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            Backend::Neon => unsafe { neon::hash_group_neon::<S, WIDE>(init, length, msgs) },
+        }
+    }
+}
+
+/// Returns the number of steps needed to compress `msg`: one per full block,
+/// plus the last block.
+#[inline(always)]
+const fn blocks(msg: &[u8]) -> usize {
+    msg.len() / 8 + 1
+}
+
+/// Returns the number of steps of every message, and the smallest and largest
+/// of them.
+#[inline(always)]
+const fn steps(msgs: &[&[u8]]) -> ([usize; MAX_LANES], usize, usize) {
+    let mut steps = [0; MAX_LANES];
+    let mut min = if msgs.is_empty() { 0 } else { usize::MAX };
+    let mut max = 0;
+    let mut l = 0;
+    while l < msgs.len() {
+        steps[l] = blocks(msgs[l]);
+        if steps[l] < min {
+            min = steps[l];
+        }
+        if steps[l] > max {
+            max = steps[l];
+        }
+        l += 1;
+    }
+    (steps, min, max)
+}
+
+/// Returns the `j`-th block of `msg`: either a full block, or the last block
+/// holding the total length (`length` bytes were hashed before `msg`) and the
+/// trailing bytes.
+#[inline(always)]
+const fn block(msg: &[u8], length: usize, j: usize) -> u64 {
+    let (_, rest) = msg.split_at(j * 8);
+    if let Some(block) = rest.first_chunk::<8>() {
+        return u64::from_le_bytes(*block);
+    }
+
+    // Same loads as `u8to64_le`, for the 0 to 7 trailing bytes.
+    let mut out = ((length + msg.len()) as u64 & 0xff) << 56;
+    let mut i = 0;
+    if let Some(bytes) = rest.first_chunk::<4>() {
+        out |= u32::from_le_bytes(*bytes) as u64;
+        i = 4;
+    }
+    if i + 1 < rest.len() {
+        out |= (u16::from_le_bytes([rest[i], rest[i + 1]]) as u64) << (8 * i);
+        i += 2;
+    }
+    if i < rest.len() {
+        out |= (rest[i] as u64) << (8 * i);
+    }
+    out
+}
+
+#[inline(always)]
+const fn sipround(mut v: [u64; 4]) -> [u64; 4] {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13);
+    v[1] ^= v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16);
+    v[3] ^= v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21);
+    v[3] ^= v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17);
+    v[1] ^= v[2];
+    v[2] = v[2].rotate_left(32);
+    v
+}
+
+/// Applies `rounds` rounds to every lane, one round of every lane at a time so
+/// that the lanes can be pipelined.
+#[inline(always)]
+const fn siprounds(mut v: [[u64; 4]; SCALAR_LANES], rounds: usize) -> [[u64; 4]; SCALAR_LANES] {
+    let mut r = 0;
+    while r < rounds {
+        let mut l = 0;
+        while l < SCALAR_LANES {
+            v[l] = sipround(v[l]);
+            l += 1;
+        }
+        r += 1;
+    }
+    v
+}
+
+/// Compresses one block `m[l]` into every lane.
+#[inline(always)]
+const fn compress<S: Sip>(
+    mut v: [[u64; 4]; SCALAR_LANES],
+    m: [u64; SCALAR_LANES],
+) -> [[u64; 4]; SCALAR_LANES] {
+    let mut l = 0;
+    while l < SCALAR_LANES {
+        v[l][3] ^= m[l];
+        l += 1;
+    }
+    v = siprounds(v, S::C_ROUNDS);
+    let mut l = 0;
+    while l < SCALAR_LANES {
+        v[l][0] ^= m[l];
+        l += 1;
+    }
+    v
+}
+
+/// Hashes up to four messages with interleaved scalar pipelines.
+pub(crate) const fn hash_group<S: Sip, const WIDE: bool>(
+    init: [u64; 4],
+    length: usize,
+    msgs: &[&[u8]],
+) -> [[u64; 2]; MAX_LANES] {
+    assert!(msgs.len() <= SCALAR_LANES);
+
+    let mut v = [init; SCALAR_LANES];
+    let (steps, min, max) = steps(msgs);
+
+    // Every lane has a full block up to the last block of the shortest
+    // message, so the common case needs no check. The loops go over all the
+    // lanes, the missing messages having no step, so that they are unrolled.
+    let mut j = 0;
+    while j + 1 < min {
+        let mut m = [0; SCALAR_LANES];
+        let mut l = 0;
+        while l < SCALAR_LANES {
+            if l < msgs.len() {
+                m[l] = block(msgs[l], length, j);
+            }
+            l += 1;
+        }
+        v = compress::<S>(v, m);
+        j += 1;
+    }
+    while j < max {
+        let mut m = [0; SCALAR_LANES];
+        let mut l = 0;
+        while l < SCALAR_LANES {
+            if j < steps[l] {
+                m[l] = block(msgs[l], length, j);
+            }
+            l += 1;
+        }
+        let old = v;
+        v = compress::<S>(v, m);
+        let mut l = 0;
+        while l < SCALAR_LANES {
+            if j >= steps[l] {
+                v[l] = old[l];
+            }
+            l += 1;
+        }
+        j += 1;
+    }
+
+    let mut out = [[0; 2]; MAX_LANES];
+    let mut l = 0;
+    while l < SCALAR_LANES {
+        v[l][2] ^= if WIDE { 0xee } else { 0xff };
+        l += 1;
+    }
+    v = siprounds(v, S::D_ROUNDS);
+    let mut l = 0;
+    while l < SCALAR_LANES {
+        out[l][0] = v[l][0] ^ v[l][1] ^ v[l][2] ^ v[l][3];
+        l += 1;
+    }
+    if WIDE {
+        let mut l = 0;
+        while l < SCALAR_LANES {
+            v[l][1] ^= 0xdd;
+            l += 1;
+        }
+        v = siprounds(v, S::D_ROUNDS);
+        let mut l = 0;
+        while l < SCALAR_LANES {
+            out[l][1] = v[l][0] ^ v[l][1] ^ v[l][2] ^ v[l][3];
+            l += 1;
+        }
+    }
+    out
+}
+
+/// The operations on a vector of `LANES` `u64`s needed by SipHash.
+///
+/// The methods are always inlined into the `#[target_feature]` functions
+/// using them, which makes the intrinsics available.
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+trait Lanes: Copy {
+    const LANES: usize;
+
+    unsafe fn splat(x: u64) -> Self;
+    /// Builds a vector from the first `LANES` elements.
+    unsafe fn from_array(x: [u64; MAX_LANES]) -> Self;
+    /// Stores into the first `LANES` elements.
+    unsafe fn store(self, out: &mut [u64; MAX_LANES]);
+    unsafe fn add(self, other: Self) -> Self;
+    unsafe fn xor(self, other: Self) -> Self;
+    /// Rotates left by `L` bits, `R` being `64 - L`.
+    unsafe fn rotl<const L: i32, const R: i32>(self) -> Self;
+    #[inline(always)]
+    unsafe fn rotl16(self) -> Self {
+        self.rotl::<16, 48>()
+    }
+    unsafe fn rotl32(self) -> Self;
+    /// Takes the bits of `a` where `mask` is set, and those of `b` elsewhere.
+    unsafe fn select(mask: Self, a: Self, b: Self) -> Self;
+}
+
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+#[inline(always)]
+unsafe fn siprounds_simd<V: Lanes>(v: &mut [V; 4], rounds: usize) {
+    let [mut v0, mut v1, mut v2, mut v3] = *v;
+    for _ in 0..rounds {
+        v0 = v0.add(v1);
+        v1 = v1.rotl::<13, 51>();
+        v1 = v1.xor(v0);
+        v0 = v0.rotl32();
+        v2 = v2.add(v3);
+        v3 = v3.rotl16();
+        v3 = v3.xor(v2);
+        v0 = v0.add(v3);
+        v3 = v3.rotl::<21, 43>();
+        v3 = v3.xor(v0);
+        v2 = v2.add(v1);
+        v1 = v1.rotl::<17, 47>();
+        v1 = v1.xor(v2);
+        v2 = v2.rotl32();
+    }
+    *v = [v0, v1, v2, v3];
+}
+
+/// The SIMD counterpart of [`hash_group`].
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+#[inline(always)]
+unsafe fn hash_group_simd<V: Lanes, S: Sip, const WIDE: bool>(
+    init: [u64; 4],
+    length: usize,
+    msgs: &[&[u8]],
+) -> [[u64; 2]; MAX_LANES] {
+    debug_assert!(msgs.len() <= V::LANES);
+
+    let mut v = [
+        V::splat(init[0]),
+        V::splat(init[1]),
+        V::splat(init[2]),
+        V::splat(init[3]),
+    ];
+    let (steps, min, max) = steps(msgs);
+
+    let mut j = 0;
+    while j + 1 < min {
+        let mut m = [0; MAX_LANES];
+        for (m, msg) in m.iter_mut().zip(msgs) {
+            *m = block(msg, length, j);
+        }
+        let m = V::from_array(m);
+        v[3] = v[3].xor(m);
+        siprounds_simd(&mut v, S::C_ROUNDS);
+        v[0] = v[0].xor(m);
+        j += 1;
+    }
+    while j < max {
+        let mut m = [0; MAX_LANES];
+        let mut mask = [0; MAX_LANES];
+        for (l, msg) in msgs.iter().enumerate() {
+            if j < steps[l] {
+                m[l] = block(msg, length, j);
+                mask[l] = !0;
+            }
+        }
+        let m = V::from_array(m);
+        let mask = V::from_array(mask);
+
+        let old = v;
+        v[3] = v[3].xor(m);
+        siprounds_simd(&mut v, S::C_ROUNDS);
+        v[0] = v[0].xor(m);
+        for (v, old) in v.iter_mut().zip(old) {
+            *v = V::select(mask, *v, old);
+        }
+        j += 1;
+    }
+
+    let mut h = [[0; MAX_LANES]; 2];
+    v[2] = v[2].xor(V::splat(if WIDE { 0xee } else { 0xff }));
+    siprounds_simd(&mut v, S::D_ROUNDS);
+    v[0].xor(v[1]).xor(v[2]).xor(v[3]).store(&mut h[0]);
+    if WIDE {
+        v[1] = v[1].xor(V::splat(0xdd));
+        siprounds_simd(&mut v, S::D_ROUNDS);
+        v[0].xor(v[1]).xor(v[2]).xor(v[3]).store(&mut h[1]);
+    }
+
+    let mut out = [[0; 2]; MAX_LANES];
+    for (l, out) in out.iter_mut().enumerate() {
+        *out = [h[0][l], h[1][l]];
+    }
+    out
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    use super::{hash_group_simd, Lanes, MAX_LANES};
+    use crate::Sip;
+
+    impl Lanes for __m128i {
+        const LANES: usize = 2;
+
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            _mm_set1_epi64x(x as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn from_array(x: [u64; MAX_LANES]) -> Self {
+            _mm_set_epi64x(x[1] as i64, x[0] as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64; MAX_LANES]) {
+            _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, self)
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, other: Self) -> Self {
+            _mm_add_epi64(self, other)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, other: Self) -> Self {
+            _mm_xor_si128(self, other)
+        }
+
+        #[inline(always)]
+        unsafe fn rotl<const L: i32, const R: i32>(self) -> Self {
+            _mm_or_si128(_mm_slli_epi64::<L>(self), _mm_srli_epi64::<R>(self))
+        }
+
+        #[inline(always)]
+        unsafe fn rotl32(self) -> Self {
+            _mm_shuffle_epi32::<0b10_11_00_01>(self)
+        }
+
+        #[inline(always)]
+        unsafe fn select(mask: Self, a: Self, b: Self) -> Self {
+            _mm_or_si128(_mm_and_si128(mask, a), _mm_andnot_si128(mask, b))
+        }
+    }
+
+    impl Lanes for __m256i {
+        const LANES: usize = 4;
+
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            _mm256_set1_epi64x(x as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn from_array(x: [u64; MAX_LANES]) -> Self {
+            _mm256_set_epi64x(x[3] as i64, x[2] as i64, x[1] as i64, x[0] as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64; MAX_LANES]) {
+            _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, self)
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, other: Self) -> Self {
+            _mm256_add_epi64(self, other)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, other: Self) -> Self {
+            _mm256_xor_si256(self, other)
+        }
+
+        #[inline(always)]
+        unsafe fn rotl<const L: i32, const R: i32>(self) -> Self {
+            _mm256_or_si256(_mm256_slli_epi64::<L>(self), _mm256_srli_epi64::<R>(self))
+        }
+
+        #[inline(always)]
+        unsafe fn rotl16(self) -> Self {
+            // Moves every byte two places up, within each 64-bit lane.
+            let bytes = _mm256_setr_epi8(
+                6, 7, 0, 1, 2, 3, 4, 5, 14, 15, 8, 9, 10, 11, 12, 13, //
+                6, 7, 0, 1, 2, 3, 4, 5, 14, 15, 8, 9, 10, 11, 12, 13,
+            );
+            _mm256_shuffle_epi8(self, bytes)
+        }
+
+        #[inline(always)]
+        unsafe fn rotl32(self) -> Self {
+            _mm256_shuffle_epi32::<0b10_11_00_01>(self)
+        }
+
+        #[inline(always)]
+        unsafe fn select(mask: Self, a: Self, b: Self) -> Self {
+            _mm256_or_si256(_mm256_and_si256(mask, a), _mm256_andnot_si256(mask, b))
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The CPU must support SSE2.
+    #[target_feature(enable = "sse2")]
+    pub(crate) unsafe fn hash_group_sse2<S: Sip, const WIDE: bool>(
+        init: [u64; 4],
+        length: usize,
+        msgs: &[&[u8]],
+    ) -> [[u64; 2]; MAX_LANES] {
+        hash_group_simd::<__m128i, S, WIDE>(init, length, msgs)
+    }
+
+    /// # Safety
+    ///
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(crate) unsafe fn hash_group_avx2<S: Sip, const WIDE: bool>(
+        init: [u64; 4],
+        length: usize,
+        msgs: &[&[u8]],
+    ) -> [[u64; 2]; MAX_LANES] {
+        hash_group_simd::<__m256i, S, WIDE>(init, length, msgs)
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon {
+    use core::arch::aarch64::*;
+
+    use super::{hash_group_simd, Lanes, MAX_LANES};
+    use crate::Sip;
+
+    impl Lanes for uint64x2_t {
+        const LANES: usize = 2;
+
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            vdupq_n_u64(x)
+        }
+
+        #[inline(always)]
+        unsafe fn from_array(x: [u64; MAX_LANES]) -> Self {
+            vcombine_u64(vcreate_u64(x[0]), vcreate_u64(x[1]))
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64; MAX_LANES]) {
+            vst1q_u64(out.as_mut_ptr(), self)
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, other: Self) -> Self {
+            vaddq_u64(self, other)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, other: Self) -> Self {
+            veorq_u64(self, other)
+        }
+
+        #[inline(always)]
+        unsafe fn rotl<const L: i32, const R: i32>(self) -> Self {
+            vsriq_n_u64::<R>(vshlq_n_u64::<L>(self), self)
+        }
+
+        #[inline(always)]
+        unsafe fn rotl32(self) -> Self {
+            vreinterpretq_u64_u32(vrev64q_u32(vreinterpretq_u32_u64(self)))
+        }
+
+        #[inline(always)]
+        unsafe fn select(mask: Self, a: Self, b: Self) -> Self {
+            vbslq_u64(mask, a, b)
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The CPU must support NEON.
+    #[target_feature(enable = "neon")]
+    pub(crate) unsafe fn hash_group_neon<S: Sip, const WIDE: bool>(
+        init: [u64; 4],
+        length: usize,
+        msgs: &[&[u8]],
+    ) -> [[u64; 2]; MAX_LANES] {
+        hash_group_simd::<uint64x2_t, S, WIDE>(init, length, msgs)
+    }
+}
@@ -0,0 +1,529 @@
+//! Perfect hash maps and sets with `&str` keys, built at compile time.
+//!
+//! [`ConstMap`] and [`ConstSet`] are built by a `const fn`, so a static lookup
+//! table is a `const` item, without build scripts or procedural macros:
+//!
+//! ```rust
+//! use const_siphasher::phf::ConstMap;
+//!
+//! const KEYWORDS: ConstMap<u8, 4> =
+//!     ConstMap::new([("fn", 0), ("let", 1), ("match", 2), ("struct", 3)]);
+//!
+//! assert_eq!(KEYWORDS.get("let"), Some(&1));
+//! assert_eq!(KEYWORDS.get("const"), None);
+//! ```
+//!
+//! The tables use the CHD algorithm ("hash, displace and compress", Belazzougui,
+//! Botelho and Dietzfelbinger): every key is hashed with a 128-bit SipHash-1-3
+//! keyed off a seed, the first 32 bits picking one of `N` buckets and the other
+//! two words being mixed with the displacement of that bucket to pick a slot.
+//! The builder tries seeds until it finds displacements that send every key to
+//! its own slot, so a lookup is one hash, two array reads and a comparison.
+//!
+//! The search runs in the compile-time interpreter, which is slow: tables with
+//! more than a few thousand keys hit the `long_running_const_eval` lint, which
+//! can be allowed on the `const` item.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+
+use crate::sip128::SipHasher13;
+
+/// The number of seeds tried before giving up.
+///
+/// With as many buckets as keys, almost every seed works for keys that are
+/// all distinct.
+const MAX_SEEDS: u64 = 1 << 10;
+
+/// The bucket of a key and the two words mixed with its displacement.
+#[derive(Clone, Copy)]
+struct Hashes {
+    g: u32,
+    f1: u32,
+    f2: u32,
+}
+
+#[inline]
+const fn hash(seed: u64, key: &str) -> Hashes {
+    let h = SipHasher13::new_with_keys(seed, 0).hash(key.as_bytes());
+    Hashes {
+        g: (h.h1 >> 32) as u32,
+        f1: h.h1 as u32,
+        f2: h.h2 as u32,
+    }
+}
+
+#[inline]
+const fn displace(f1: u32, f2: u32, (d1, d2): (u32, u32)) -> u32 {
+    d2.wrapping_add(f1.wrapping_mul(d1)).wrapping_add(f2)
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// The displacements of the buckets, and the index of the key in every slot.
+struct Table<const N: usize> {
+    disps: [(u32, u32); N],
+    slots: [u32; N],
+}
+
+/// Looks for a seed giving a perfect hash function for `keys`.
+///
+/// # Panics
+///
+/// If two keys are equal, or if no seed works.
+const fn search<const N: usize>(keys: &[&str; N]) -> (u64, Table<N>) {
+    assert!(N <= u32::MAX as usize, "too many keys");
+
+    let mut seed = 0;
+    while seed < MAX_SEEDS {
+        if let Some(table) = try_seed(keys, seed) {
+            return (seed, table);
+        }
+        seed += 1;
+    }
+    panic!("no perfect hash function found for the keys");
+}
+
+const fn try_seed<const N: usize>(keys: &[&str; N], seed: u64) -> Option<Table<N>> {
+    let mut hashes = [Hashes { g: 0, f1: 0, f2: 0 }; N];
+    let mut bucket_lens = [0; N];
+    let mut i = 0;
+    while i < N {
+        hashes[i] = hash(seed, keys[i]);
+        hashes[i].g %= N as u32;
+        bucket_lens[hashes[i].g as usize] += 1;
+        i += 1;
+    }
+
+    // The keys, sorted by bucket: bucket `b` holds the `bucket_lens[b]` keys
+    // starting at `by_bucket[starts[b]]`.
+    let mut starts = [0; N];
+    let mut b = 1;
+    while b < N {
+        starts[b] = starts[b - 1] + bucket_lens[b - 1];
+        b += 1;
+    }
+    let mut by_bucket = [0; N];
+    let mut filled = [0; N];
+    let mut i = 0;
+    while i < N {
+        let b = hashes[i].g as usize;
+        by_bucket[starts[b] + filled[b]] = i;
+        filled[b] += 1;
+        i += 1;
+    }
+
+    // The non-empty buckets, largest first, as the large buckets are the
+    // hardest to place: there are `count[len - 1]` buckets of length `len`.
+    let mut count = [0; N];
+    let mut b = 0;
+    while b < N {
+        if bucket_lens[b] > 0 {
+            count[bucket_lens[b] - 1] += 1;
+        }
+        b += 1;
+    }
+    let mut first = [0; N];
+    let mut len = N;
+    let mut n = 0;
+    while len > 0 {
+        first[len - 1] = n;
+        n += count[len - 1];
+        len -= 1;
+    }
+    let mut order = [0; N];
+    let mut b = 0;
+    while b < N {
+        if bucket_lens[b] > 0 {
+            let len = bucket_lens[b];
+            order[first[len - 1]] = b;
+            first[len - 1] += 1;
+        }
+        b += 1;
+    }
+
+    let mut table = Table {
+        disps: [(0, 0); N],
+        slots: [0; N],
+    };
+    let mut taken = [false; N];
+    // `tried[slot] == generation` if the slot is used by the current attempt.
+    let mut tried = [0u64; N];
+    let mut generation = 0;
+
+    let mut k = 0;
+    while k < n {
+        let b = order[k];
+        let bucket = by_bucket.split_at(starts[b]).1.split_at(bucket_lens[b]).0;
+
+        // Keys with the same hashes can't be told apart by any displacement.
+        let mut i = 0;
+        while i < bucket.len() {
+            let mut j = 0;
+            while j < i {
+                let (x, y) = (hashes[bucket[i]], hashes[bucket[j]]);
+                if x.f1 == y.f1 && x.f2 == y.f2 {
+                    if str_eq(keys[bucket[i]], keys[bucket[j]]) {
+                        panic!("duplicate key");
+                    }
+                    return None;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+
+        let mut placed = false;
+        let mut d1 = 0;
+        'search: while d1 < N as u32 {
+            let mut d2 = 0;
+            while d2 < N as u32 {
+                generation += 1;
+                let mut i = 0;
+                while i < bucket.len() {
+                    let h = hashes[bucket[i]];
+                    let slot = (displace(h.f1, h.f2, (d1, d2)) % N as u32) as usize;
+                    if taken[slot] || tried[slot] == generation {
+                        break;
+                    }
+                    tried[slot] = generation;
+                    i += 1;
+                }
+                if i == bucket.len() {
+                    let mut i = 0;
+                    while i < bucket.len() {
+                        let h = hashes[bucket[i]];
+                        let slot = (displace(h.f1, h.f2, (d1, d2)) % N as u32) as usize;
+                        taken[slot] = true;
+                        table.slots[slot] = bucket[i] as u32;
+                        i += 1;
+                    }
+                    table.disps[b] = (d1, d2);
+                    placed = true;
+                    break 'search;
+                }
+                d2 += 1;
+            }
+            d1 += 1;
+        }
+        if !placed {
+            return None;
+        }
+        k += 1;
+    }
+    Some(table)
+}
+
+/// An immutable map from `&'static str` keys to values of type `V`, with `N`
+/// entries, using a perfect hash function found at compile time.
+///
+/// The entries are kept in the order they were given in, which is the order
+/// of iteration.
+///
+/// # Examples
+///
+/// ```rust
+/// use const_siphasher::phf::ConstMap;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Token {
+///     If,
+///     Else,
+///     While,
+/// }
+///
+/// const TOKENS: ConstMap<Token, 3> =
+///     ConstMap::new([("if", Token::If), ("else", Token::Else), ("while", Token::While)]);
+/// const WHILE: Option<&Token> = TOKENS.get("while");
+///
+/// assert_eq!(WHILE, Some(&Token::While));
+/// assert!(!TOKENS.contains_key("for"));
+/// assert_eq!(TOKENS.keys().collect::<Vec<_>>(), ["if", "else", "while"]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ConstMap<V, const N: usize> {
+    seed: u64,
+    disps: [(u32, u32); N],
+    slots: [u32; N],
+    entries: [(&'static str, V); N],
+}
+
+impl<V, const N: usize> ConstMap<V, N> {
+    /// Builds a map from its entries.
+    ///
+    /// # Panics
+    ///
+    /// If two keys are equal. In a `const` item, this is a compile error.
+    pub const fn new(entries: [(&'static str, V); N]) -> ConstMap<V, N> {
+        let mut keys = [""; N];
+        let mut i = 0;
+        while i < N {
+            keys[i] = entries[i].0;
+            i += 1;
+        }
+        let (seed, table) = search(&keys);
+        ConstMap {
+            seed,
+            disps: table.disps,
+            slots: table.slots,
+            entries,
+        }
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the map has no entries.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the index of `key` in the entries, if it is in the map.
+    #[inline]
+    pub const fn get_index(&self, key: &str) -> Option<usize> {
+        if N == 0 {
+            return None;
+        }
+        let h = hash(self.seed, key);
+        let disp = self.disps[(h.g % N as u32) as usize];
+        let index = self.slots[(displace(h.f1, h.f2, disp) % N as u32) as usize] as usize;
+        if str_eq(self.entries[index].0, key) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of `key`, if it is in the map.
+    #[inline]
+    pub const fn get(&self, key: &str) -> Option<&V> {
+        match self.get_index(key) {
+            Some(index) => Some(&self.entries[index].1),
+            None => None,
+        }
+    }
+
+    /// Returns the entry of `key`, if it is in the map.
+    #[inline]
+    pub const fn get_entry(&self, key: &str) -> Option<(&'static str, &V)> {
+        match self.get_index(key) {
+            Some(index) => Some((self.entries[index].0, &self.entries[index].1)),
+            None => None,
+        }
+    }
+
+    /// Returns `true` if `key` is in the map.
+    #[inline]
+    pub const fn contains_key(&self, key: &str) -> bool {
+        self.get_index(key).is_some()
+    }
+
+    /// Returns the entries, in the order they were given in.
+    #[inline]
+    pub const fn entries(&self) -> &[(&'static str, V); N] {
+        &self.entries
+    }
+
+    /// Returns an iterator over the entries.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, V> {
+        Keys {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the values.
+    #[inline]
+    pub fn values(&self) -> Values<'_, V> {
+        Values {
+            iter: self.entries.iter(),
+        }
+    }
+}
+
+impl<V: fmt::Debug, const N: usize> fmt::Debug for ConstMap<V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, V, const N: usize> IntoIterator for &'a ConstMap<V, N> {
+    type Item = (&'static str, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, V> {
+        self.iter()
+    }
+}
+
+macro_rules! impl_iterator {
+    ($name:ident, $item:ty, |$entry:pat_param| $map:expr) => {
+        impl<'a, V> Iterator for $name<'a, V> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<$item> {
+                self.iter.next().map(|$entry| $map)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<'a, V> DoubleEndedIterator for $name<'a, V> {
+            #[inline]
+            fn next_back(&mut self) -> Option<$item> {
+                self.iter.next_back().map(|$entry| $map)
+            }
+        }
+
+        impl<'a, V> ExactSizeIterator for $name<'a, V> {}
+
+        impl<'a, V> FusedIterator for $name<'a, V> {}
+    };
+}
+
+/// An iterator over the entries of a [`ConstMap`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a, V> {
+    iter: slice::Iter<'a, (&'static str, V)>,
+}
+
+impl_iterator!(Iter, (&'static str, &'a V), |(key, value)| (*key, value));
+
+/// An iterator over the keys of a [`ConstMap`] or a [`ConstSet`].
+#[derive(Debug, Clone)]
+pub struct Keys<'a, V> {
+    iter: slice::Iter<'a, (&'static str, V)>,
+}
+
+impl_iterator!(Keys, &'static str, |(key, _)| *key);
+
+/// An iterator over the values of a [`ConstMap`].
+#[derive(Debug, Clone)]
+pub struct Values<'a, V> {
+    iter: slice::Iter<'a, (&'static str, V)>,
+}
+
+impl_iterator!(Values, &'a V, |(_, value)| value);
+
+/// An immutable set of `&'static str` with `N` elements, using a perfect hash
+/// function found at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// use const_siphasher::phf::ConstSet;
+///
+/// const RESERVED: ConstSet<3> = ConstSet::new(["abstract", "final", "virtual"]);
+///
+/// assert!(RESERVED.contains("final"));
+/// assert!(!RESERVED.contains("fn"));
+/// ```
+#[derive(Clone, Copy)]
+pub struct ConstSet<const N: usize> {
+    map: ConstMap<(), N>,
+}
+
+impl<const N: usize> ConstSet<N> {
+    /// Builds a set from its elements.
+    ///
+    /// # Panics
+    ///
+    /// If two elements are equal. In a `const` item, this is a compile error.
+    pub const fn new(keys: [&'static str; N]) -> ConstSet<N> {
+        let mut entries = [("", ()); N];
+        let mut i = 0;
+        while i < N {
+            entries[i].0 = keys[i];
+            i += 1;
+        }
+        ConstSet {
+            map: ConstMap::new(entries),
+        }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the set has no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns `true` if `key` is in the set.
+    #[inline]
+    pub const fn contains(&self, key: &str) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the element equal to `key`, if it is in the set.
+    #[inline]
+    pub const fn get(&self, key: &str) -> Option<&'static str> {
+        match self.map.get_entry(key) {
+            Some((key, _)) => Some(key),
+            None => None,
+        }
+    }
+
+    /// Returns the index of `key` in the elements, if it is in the set.
+    #[inline]
+    pub const fn get_index(&self, key: &str) -> Option<usize> {
+        self.map.get_index(key)
+    }
+
+    /// Returns an iterator over the elements, in the order they were given in.
+    #[inline]
+    pub fn iter(&self) -> Keys<'_, ()> {
+        self.map.keys()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ConstSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a ConstSet<N> {
+    type Item = &'static str;
+    type IntoIter = Keys<'a, ()>;
+
+    #[inline]
+    fn into_iter(self) -> Keys<'a, ()> {
+        self.iter()
+    }
+}
@@ -0,0 +1,370 @@
+//! Keyed placement of keys on nodes or buckets.
+//!
+//! * Rendezvous hashing (highest random weight, Thaler and Ravishankar)
+//!   scores every node for a key, and picks the node with the highest score.
+//!   Removing a node only moves the keys that were on it, and nodes can have
+//!   weights.
+//! * Jump consistent hashing (Lamping and Veach) maps a 64-bit hash to one of
+//!   `n` numbered buckets without any table, and only moves `1 / n` of the
+//!   keys when a bucket is added at the end.
+//! * With the `alloc` feature, [`HashRing`] is a consistent hash ring (Karger
+//!   et al.) with virtual nodes, for lookups in `O(log n)` over many nodes.
+//!
+//! The scores and hashes come from SipHash-2-4 (SipHash-1-3 for the ring), so
+//! clients that don't know the key can't predict, or skew, the placement.
+//!
+//! ```rust
+//! use const_siphasher::sharding::{jump_consistent_hash, rendezvous_pick};
+//! use const_siphasher::sip::SipHasher24;
+//!
+//! let hasher = SipHasher24::new_with_keys(1, 2);
+//! let nodes = ["db-1", "db-2", "db-3"];
+//! let node = rendezvous_pick(&hasher, b"user:42", &nodes).unwrap();
+//! assert!(nodes.contains(node));
+//!
+//! let bucket = jump_consistent_hash(hasher.hash(b"user:42"), 10);
+//! assert!(bucket < 10);
+//! ```
+
+use crate::sip::SipHasher24;
+
+/// Returns the score of `node` for `key`: the hash of the length of the
+/// node name, the node name and the key.
+///
+/// The length prefix keeps the node `"a"` with the key `"bc"` apart from the
+/// node `"ab"` with the key `"c"`.
+#[inline]
+pub const fn rendezvous_score(hasher: &SipHasher24, node: &[u8], key: &[u8]) -> u64 {
+    let mut hasher = *hasher;
+    hasher.write_usize_as_u64(node.len());
+    hasher.write(node);
+    hasher.write(key);
+    hasher.finish()
+}
+
+/// Returns the node with the highest [score](rendezvous_score) for `key`, or
+/// `None` if there are no nodes.
+///
+/// Ties, which are very unlikely, go to the first node.
+pub fn rendezvous_pick<'a, N: AsRef<[u8]>>(
+    hasher: &SipHasher24,
+    key: &[u8],
+    nodes: &'a [N],
+) -> Option<&'a N> {
+    let mut best: Option<(&N, u64)> = None;
+    for node in nodes {
+        let score = rendezvous_score(hasher, node.as_ref(), key);
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((node, score));
+        }
+    }
+    best.map(|(node, _)| node)
+}
+
+/// Returns the node picked for `key` by weighted rendezvous hashing, or `None`
+/// if no node has a positive weight.
+///
+/// Every node gets a share of the keys proportional to its weight: the score
+/// of a node is `-weight / ln(u)`, `u` being its [score](rendezvous_score)
+/// mapped to `(0, 1)` (Schindelhauer and Schomaker). Nodes with a weight that
+/// isn't positive and finite never get any key.
+pub fn weighted_rendezvous_pick<'a, N: AsRef<[u8]>>(
+    hasher: &SipHasher24,
+    key: &[u8],
+    nodes: &'a [(N, f64)],
+) -> Option<&'a N> {
+    let mut best: Option<(&N, f64)> = None;
+    for (node, weight) in nodes {
+        if !(*weight > 0.0 && weight.is_finite()) {
+            continue;
+        }
+        let u = unit_interval(rendezvous_score(hasher, node.as_ref(), key));
+        let score = -weight / ln(u);
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((node, score));
+        }
+    }
+    best.map(|(node, _)| node)
+}
+
+/// Maps a hash to a number in `(0, 1)`, from its 53 high bits.
+#[inline]
+fn unit_interval(hash: u64) -> f64 {
+    ((hash >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+}
+
+/// The natural logarithm of a positive, finite and normal `x`.
+///
+/// `core` has no `ln`, so this splits `x` into `m * 2^e` with `m` in
+/// `[sqrt(2)/2, sqrt(2))`, and sums the series of
+/// `ln(m) = 2 * atanh((m - 1) / (m + 1))`.
+pub(crate) fn ln(x: f64) -> f64 {
+    const MANTISSA_BITS: u32 = 52;
+    const EXPONENT_BIAS: i64 = 1023;
+
+    let bits = x.to_bits();
+    let mut exponent = (bits >> MANTISSA_BITS) as i64 - EXPONENT_BIAS;
+    let mut m =
+        f64::from_bits(bits & ((1 << MANTISSA_BITS) - 1) | (EXPONENT_BIAS as u64) << MANTISSA_BITS);
+    if m > core::f64::consts::SQRT_2 {
+        m /= 2.0;
+        exponent += 1;
+    }
+
+    // |s| < 0.172, so the terms shrink by at least 34 times each.
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = 0.0;
+    let mut k = 1.0;
+    while k < 25.0 {
+        sum += term / k;
+        term *= s2;
+        k += 2.0;
+    }
+    exponent as f64 * core::f64::consts::LN_2 + 2.0 * sum
+}
+
+/// Returns the bucket of `hash` among `buckets` buckets numbered from 0, with
+/// jump consistent hashing.
+///
+/// `hash` should be the output of a keyed hash, such as
+/// [`SipHasher24::hash`]. When the number of buckets goes from `n` to `n + 1`,
+/// only the keys that move to the new bucket `n` change buckets.
+///
+/// # Panics
+///
+/// If `buckets` is zero.
+pub const fn jump_consistent_hash(hash: u64, buckets: u32) -> u32 {
+    assert!(buckets > 0, "there must be at least one bucket");
+
+    let mut key = hash;
+    let mut b: i64 = -1;
+    let mut j: i64 = 0;
+    while j < buckets as i64 {
+        b = j;
+        key = key.wrapping_mul(2862933555777941757).wrapping_add(1);
+        j = ((b + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    b as u32
+}
+
+#[cfg(feature = "alloc")]
+pub use self::ring::HashRing;
+
+#[cfg(feature = "alloc")]
+mod ring {
+    use alloc::vec::Vec;
+    use core::fmt;
+
+    use crate::sip::SipHasher13;
+    use crate::SipKey;
+
+    /// A consistent hash ring, placing every node at `vnodes` points of a
+    /// ring of 64-bit hashes.
+    ///
+    /// A key belongs to the node of the first point at or after its hash,
+    /// which is found by binary search. Adding or removing a node only moves
+    /// the keys between the points of that node and the previous points, that
+    /// is about `1 / n` of the keys with `n` nodes. The more virtual nodes,
+    /// the more even the shares of the nodes: with 100 to 200 of them, the
+    /// shares are usually within 10 to 20% of the average.
+    ///
+    /// The points and the keys are hashed with SipHash-1-3 keyed off a secret
+    /// key, so clients can't tell which keys go to which node.
+    ///
+    /// ```rust
+    /// use const_siphasher::sharding::HashRing;
+    ///
+    /// let mut ring = HashRing::new_with_keys(1, 2, 160);
+    /// ring.add("cache-1");
+    /// ring.add("cache-2");
+    /// ring.add("cache-3");
+    ///
+    /// let node = *ring.get(b"user:42").unwrap();
+    /// ring.remove("cache-2");
+    /// if node != "cache-2" {
+    ///     assert_eq!(ring.get(b"user:42"), Some(&node));
+    /// }
+    /// ```
+    #[derive(Clone)]
+    pub struct HashRing<N> {
+        hasher: SipHasher13,
+        vnodes: u32,
+        nodes: Vec<N>,
+        /// The points of the nodes, and the index of their node, sorted.
+        points: Vec<(u64, usize)>,
+    }
+
+    impl<N: AsRef<[u8]>> HashRing<N> {
+        /// Creates an empty ring with the two keys set to 0, placing every
+        /// node at `vnodes` points.
+        ///
+        /// # Panics
+        ///
+        /// If `vnodes` is zero.
+        #[inline]
+        pub fn new(vnodes: u32) -> HashRing<N> {
+            HashRing::new_with_keys(0, 0, vnodes)
+        }
+
+        /// Creates an empty ring keyed off the provided keys.
+        ///
+        /// See [`new`](Self::new) for `vnodes`.
+        #[inline]
+        pub fn new_with_keys(key0: u64, key1: u64, vnodes: u32) -> HashRing<N> {
+            HashRing::new_with_sip_key(SipKey::from_u64s(key0, key1), vnodes)
+        }
+
+        /// Creates an empty ring keyed off the provided [`SipKey`].
+        ///
+        /// See [`new`](Self::new) for `vnodes`.
+        pub fn new_with_sip_key(key: SipKey, vnodes: u32) -> HashRing<N> {
+            assert!(vnodes > 0, "every node needs at least one virtual node");
+            HashRing {
+                hasher: SipHasher13::new_with_sip_key(key),
+                vnodes,
+                nodes: Vec::new(),
+                points: Vec::new(),
+            }
+        }
+
+        /// Returns the number of points of every node.
+        #[inline]
+        pub fn vnodes(&self) -> u32 {
+            self.vnodes
+        }
+
+        /// Returns the number of nodes.
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.nodes.len()
+        }
+
+        /// Returns `true` if the ring has no nodes.
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.nodes.is_empty()
+        }
+
+        /// Returns the nodes, in the order they were added in, except that
+        /// removing a node moves the last node in its place.
+        #[inline]
+        pub fn nodes(&self) -> &[N] {
+            &self.nodes
+        }
+
+        /// Returns `true` if the ring has a node named `node`.
+        pub fn contains<Q: AsRef<[u8]> + ?Sized>(&self, node: &Q) -> bool {
+            self.position(node.as_ref()).is_some()
+        }
+
+        fn position(&self, node: &[u8]) -> Option<usize> {
+            self.nodes.iter().position(|n| n.as_ref() == node)
+        }
+
+        /// Returns the `replica`-th point of `node`.
+        fn point(&self, node: &[u8], replica: u32) -> u64 {
+            let mut hasher = self.hasher;
+            hasher.write_usize_as_u64(node.len());
+            hasher.write(node);
+            hasher.write_u32_le(replica);
+            hasher.finish()
+        }
+
+        /// Adds `node` to the ring.
+        ///
+        /// Returns `false`, leaving the ring unchanged, if it already had a
+        /// node with the same name.
+        ///
+        /// Only the points of the new node are sorted, and then merged with
+        /// the others, in `O(vnodes log vnodes + points)`.
+        pub fn add(&mut self, node: N) -> bool {
+            if self.contains(&node) {
+                return false;
+            }
+            let index = self.nodes.len();
+            let mut new: Vec<(u64, usize)> = (0..self.vnodes)
+                .map(|replica| (self.point(node.as_ref(), replica), index))
+                .collect();
+            new.sort_unstable();
+            self.nodes.push(node);
+
+            // The (very unlikely) ties are broken by node name, so that the
+            // ring doesn't depend on the order the nodes were added in.
+            let nodes = &self.nodes;
+            let before = |&(a, i): &(u64, usize), &(b, j): &(u64, usize)| {
+                a < b || (a == b && nodes[i].as_ref() < nodes[j].as_ref())
+            };
+            let old = core::mem::take(&mut self.points);
+            let mut points = Vec::with_capacity(old.len() + new.len());
+            let (mut old, mut new) = (old.into_iter().peekable(), new.into_iter().peekable());
+            while let (Some(a), Some(b)) = (old.peek(), new.peek()) {
+                if before(b, a) {
+                    points.extend(new.next());
+                } else {
+                    points.extend(old.next());
+                }
+            }
+            points.extend(old);
+            points.extend(new);
+            self.points = points;
+            true
+        }
+
+        /// Removes the node named `node` from the ring, and returns it.
+        pub fn remove<Q: AsRef<[u8]> + ?Sized>(&mut self, node: &Q) -> Option<N> {
+            let index = self.position(node.as_ref())?;
+            let last = self.nodes.len() - 1;
+            self.points.retain(|&(_, i)| i != index);
+            for (_, i) in &mut self.points {
+                if *i == last {
+                    *i = index;
+                }
+            }
+            Some(self.nodes.swap_remove(index))
+        }
+
+        /// Returns the node that `key` belongs to, or `None` if the ring is
+        /// empty.
+        pub fn get(&self, key: &[u8]) -> Option<&N> {
+            let h = self.hasher.hash(key);
+            let i = self.points.partition_point(|&(point, _)| point < h);
+            let &(_, index) = self.points.get(i).or_else(|| self.points.first())?;
+            Some(&self.nodes[index])
+        }
+
+        /// Returns up to `count` different nodes for `key`: the node it
+        /// belongs to, followed by the next nodes around the ring, for
+        /// replicas.
+        pub fn get_n(&self, key: &[u8], count: usize) -> Vec<&N> {
+            let count = count.min(self.nodes.len());
+            let mut found = Vec::with_capacity(count);
+            if count == 0 {
+                return found;
+            }
+            let h = self.hasher.hash(key);
+            let start = self.points.partition_point(|&(point, _)| point < h);
+            let mut indices: Vec<usize> = Vec::with_capacity(count);
+            for &(_, index) in self.points[start..].iter().chain(&self.points[..start]) {
+                if !indices.contains(&index) {
+                    indices.push(index);
+                    found.push(&self.nodes[index]);
+                    if found.len() == count {
+                        break;
+                    }
+                }
+            }
+            found
+        }
+    }
+
+    impl<N: fmt::Debug> fmt::Debug for HashRing<N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("HashRing")
+                .field("vnodes", &self.vnodes)
+                .field("nodes", &self.nodes)
+                .finish_non_exhaustive()
+        }
+    }
+}
@@ -10,12 +10,14 @@
 
 //! An implementation of SipHash.
 
+use core::fmt;
 use core::hash;
 use core::marker::PhantomData;
 use core::mem;
 use core::ptr;
 
-use crate::{Sip, Sip13Rounds, Sip24Rounds};
+use crate::many::{self, Backend};
+use crate::{HashValue, Sip, Sip13Rounds, Sip24Rounds, SipCDRounds, SipKey};
 
 /// An implementation of SipHash 1-3.
 ///
@@ -35,6 +37,18 @@ pub struct SipHasher24 {
     hasher: Hasher<Sip24Rounds>,
 }
 
+/// An implementation of SipHash C-D, with a caller-chosen number of
+/// compression (`C`) and finalization (`D`) rounds.
+///
+/// `SipHasherCD<1, 3>` and `SipHasherCD<2, 4>` are equivalent to
+/// [`SipHasher13`] and [`SipHasher24`]. `SipHasherCD<4, 8>` is the
+/// conservative variant suggested in the paper.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipHasherCD<const C: usize, const D: usize> {
+    hasher: Hasher<SipCDRounds<C, D>>,
+}
+
 /// An implementation of SipHash 2-4.
 ///
 /// See: <https://www.aumasson.jp/siphash/siphash.pdf>
@@ -51,11 +65,61 @@ pub struct SipHasher24 {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SipHasher(SipHasher24);
 
-#[derive(Debug, Clone, Copy)]
+/// A [`BuildHasher`](hash::BuildHasher) creating [`SipHasher13`] instances
+/// keyed off a fixed key.
+///
+/// This can be used to plug SipHash 1-3 into `HashMap` and `HashSet`.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipBuildHasher13 {
+    hasher: SipHasher13,
+}
+
+/// A [`BuildHasher`](hash::BuildHasher) creating [`SipHasher24`] instances
+/// keyed off a fixed key.
+///
+/// This can be used to plug SipHash 2-4 into `HashMap` and `HashSet`.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipBuildHasher24 {
+    hasher: SipHasher24,
+}
+
+/// SipHash 1-3 with the initial state derived from the key ahead of time.
+///
+/// [`hash`](Self::hash) gives the same results as [`SipHasher13::hash`] with
+/// the same key, but starts every message from the cached state and reads it
+/// in one pass, without the buffering needed by incremental writes. This
+/// pays off when hashing many short messages with the same key.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyedSip13 {
+    keyed: Keyed<Sip13Rounds>,
+}
+
+/// SipHash 2-4 with the initial state derived from the key ahead of time.
+///
+/// [`hash`](Self::hash) gives the same results as [`SipHasher24::hash`] with
+/// the same key, but starts every message from the cached state and reads it
+/// in one pass, without the buffering needed by incremental writes.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyedSip24 {
+    keyed: Keyed<Sip24Rounds>,
+}
+
+#[derive(Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        into = "RawHasher<S>",
+        from = "RawHasher<S>",
+        bound(serialize = "S: Clone", deserialize = "")
+    )
+)]
 struct Hasher<S: Sip> {
-    k0: u64,
-    k1: u64,
+    key: SipKey,
     length: usize, // how many bytes we've processed
     state: State,  // hash State
     tail: u64,     // unprocessed bytes le
@@ -63,6 +127,24 @@ struct Hasher<S: Sip> {
     _marker: PhantomData<S>,
 }
 
+/// Only the key is serialized, and the state is derived from it again on
+/// deserialization, so the two can't disagree.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        into = "SipKey",
+        from = "SipKey",
+        bound(serialize = "S: Clone", deserialize = "")
+    )
+)]
+struct Keyed<S: Sip> {
+    key: SipKey,
+    state: State, // initial State for `key`
+    _marker: PhantomData<S>,
+}
+
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct State {
@@ -76,6 +158,14 @@ struct State {
     v3: u64,
 }
 
+impl State {
+    /// Returns `[v0, v1, v2, v3]`.
+    #[inline]
+    const fn to_array(self) -> [u64; 4] {
+        [self.v0, self.v1, self.v2, self.v3]
+    }
+}
+
 macro_rules! compress {
     ($state:expr) => {{
         compress!($state.v0, $state.v1, $state.v2, $state.v3)
@@ -156,27 +246,31 @@ impl SipHasher {
     }
 
     /// Creates a `SipHasher` from a 16 byte key.
-    pub fn new_with_key(key: &[u8; 16]) -> SipHasher {
-        let mut b0 = [0u8; 8];
-        let mut b1 = [0u8; 8];
-        b0.copy_from_slice(&key[0..8]);
-        b1.copy_from_slice(&key[8..16]);
-        let key0 = u64::from_le_bytes(b0);
-        let key1 = u64::from_le_bytes(b1);
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipHasher {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasher` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipHasher {
+        let (key0, key1) = key.to_u64s();
         Self::new_with_keys(key0, key1)
     }
 
     /// Get the keys used by this hasher
     pub const fn keys(&self) -> (u64, u64) {
-        (self.0.hasher.k0, self.0.hasher.k1)
+        self.0.hasher.key.to_u64s()
     }
 
     /// Get the key used by this hasher as a 16 byte vector
-    pub fn key(&self) -> [u8; 16] {
-        let mut bytes = [0u8; 16];
-        bytes[0..8].copy_from_slice(&self.0.hasher.k0.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.0.hasher.k1.to_le_bytes());
-        bytes
+    pub const fn key(&self) -> [u8; 16] {
+        self.0.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.0.hasher.key
     }
 
     /// Hash a byte array - This is the easiest and safest way to use SipHash.
@@ -186,6 +280,96 @@ impl SipHasher {
         hasher.write(bytes);
         hasher.finish()
     }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> u64 {
+        let mut hasher = self.0.hasher;
+        hasher.write_value(value);
+        hasher.finish()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> u64 {
+        self.0.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> u64 {
+        self.0.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> u64 {
+        self.0.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> u64 {
+        self.0.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.0.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.0.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the little-endian encoding of the output of
+    /// [`hash`](Self::hash) for `msg`.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 8]) -> bool {
+        crate::ct_eq(&self.hash(msg).to_le_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.0.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.0.finish_and_reset()
+    }
 }
 
 impl SipHasher13 {
@@ -204,27 +388,31 @@ impl SipHasher13 {
     }
 
     /// Creates a `SipHasher13` from a 16 byte key.
-    pub fn new_with_key(key: &[u8; 16]) -> SipHasher13 {
-        let mut b0 = [0u8; 8];
-        let mut b1 = [0u8; 8];
-        b0.copy_from_slice(&key[0..8]);
-        b1.copy_from_slice(&key[8..16]);
-        let key0 = u64::from_le_bytes(b0);
-        let key1 = u64::from_le_bytes(b1);
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipHasher13 {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasher13` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipHasher13 {
+        let (key0, key1) = key.to_u64s();
         Self::new_with_keys(key0, key1)
     }
 
     /// Get the keys used by this hasher
     pub const fn keys(&self) -> (u64, u64) {
-        (self.hasher.k0, self.hasher.k1)
+        self.hasher.key.to_u64s()
     }
 
     /// Get the key used by this hasher as a 16 byte vector
-    pub fn key(&self) -> [u8; 16] {
-        let mut bytes = [0u8; 16];
-        bytes[0..8].copy_from_slice(&self.hasher.k0.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.hasher.k1.to_le_bytes());
-        bytes
+    pub const fn key(&self) -> [u8; 16] {
+        self.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.key
     }
 
     /// Hash a byte array - This is the easiest and safest way to use SipHash.
@@ -234,6 +422,96 @@ impl SipHasher13 {
         hasher.write(bytes);
         hasher.finish()
     }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> u64 {
+        let mut hasher = self.hasher;
+        hasher.write_value(value);
+        hasher.finish()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> u64 {
+        self.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> u64 {
+        self.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> u64 {
+        self.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> u64 {
+        self.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the little-endian encoding of the output of
+    /// [`hash`](Self::hash) for `msg`.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 8]) -> bool {
+        crate::ct_eq(&self.hash(msg).to_le_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.hasher.reset_in_place();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.hasher.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.hasher.finish_and_reset()
+    }
 }
 
 impl SipHasher24 {
@@ -252,27 +530,173 @@ impl SipHasher24 {
     }
 
     /// Creates a `SipHasher24` from a 16 byte key.
-    pub fn new_with_key(key: &[u8; 16]) -> SipHasher24 {
-        let mut b0 = [0u8; 8];
-        let mut b1 = [0u8; 8];
-        b0.copy_from_slice(&key[0..8]);
-        b1.copy_from_slice(&key[8..16]);
-        let key0 = u64::from_le_bytes(b0);
-        let key1 = u64::from_le_bytes(b1);
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipHasher24 {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasher24` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipHasher24 {
+        let (key0, key1) = key.to_u64s();
+        Self::new_with_keys(key0, key1)
+    }
+
+    /// Get the keys used by this hasher
+    pub const fn keys(&self) -> (u64, u64) {
+        self.hasher.key.to_u64s()
+    }
+
+    /// Get the key used by this hasher as a 16 byte vector
+    pub const fn key(&self) -> [u8; 16] {
+        self.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.key
+    }
+
+    /// Hash a byte array - This is the easiest and safest way to use SipHash.
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> u64 {
+        let mut hasher = self.hasher;
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> u64 {
+        let mut hasher = self.hasher;
+        hasher.write_value(value);
+        hasher.finish()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> u64 {
+        self.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> u64 {
+        self.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> u64 {
+        self.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> u64 {
+        self.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the little-endian encoding of the output of
+    /// [`hash`](Self::hash) for `msg`.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 8]) -> bool {
+        crate::ct_eq(&self.hash(msg).to_le_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.hasher.reset_in_place();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.hasher.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.hasher.finish_and_reset()
+    }
+}
+
+impl<const C: usize, const D: usize> SipHasherCD<C, D> {
+    /// Creates a new `SipHasherCD` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipHasherCD` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> Self {
+        SipHasherCD {
+            hasher: Hasher::new_with_keys(key0, key1),
+        }
+    }
+
+    /// Creates a `SipHasherCD` from a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> Self {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasherCD` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> Self {
+        let (key0, key1) = key.to_u64s();
         Self::new_with_keys(key0, key1)
     }
 
     /// Get the keys used by this hasher
     pub const fn keys(&self) -> (u64, u64) {
-        (self.hasher.k0, self.hasher.k1)
+        self.hasher.key.to_u64s()
     }
 
     /// Get the key used by this hasher as a 16 byte vector
-    pub fn key(&self) -> [u8; 16] {
-        let mut bytes = [0u8; 16];
-        bytes[0..8].copy_from_slice(&self.hasher.k0.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.hasher.k1.to_le_bytes());
-        bytes
+    pub const fn key(&self) -> [u8; 16] {
+        self.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.key
     }
 
     /// Hash a byte array - This is the easiest and safest way to use SipHash.
@@ -282,14 +706,103 @@ impl SipHasher24 {
         hasher.write(bytes);
         hasher.finish()
     }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> u64 {
+        let mut hasher = self.hasher;
+        hasher.write_value(value);
+        hasher.finish()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> u64 {
+        self.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> u64 {
+        self.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> u64 {
+        self.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> u64 {
+        self.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        self.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the little-endian encoding of the output of
+    /// [`hash`](Self::hash) for `msg`.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 8]) -> bool {
+        crate::ct_eq(&self.hash(msg).to_le_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.hasher.reset_in_place();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.hasher.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.hasher.finish_and_reset()
+    }
 }
 
 impl<S: Sip> Hasher<S> {
     #[inline]
     const fn new_with_keys(key0: u64, key1: u64) -> Hasher<S> {
         let mut state = Hasher {
-            k0: key0,
-            k1: key1,
+            key: SipKey::from_u64s(key0, key1),
             length: 0,
             state: State {
                 v0: 0,
@@ -308,13 +821,33 @@ impl<S: Sip> Hasher<S> {
     #[inline]
     #[must_use]
     const fn reset(mut self) -> Self {
+        self.reset_in_place();
+        self
+    }
+
+    #[inline]
+    const fn reset_in_place(&mut self) {
+        let (k0, k1) = self.key.to_u64s();
         self.length = 0;
-        self.state.v0 = self.k0 ^ 0x736f6d6570736575;
-        self.state.v1 = self.k1 ^ 0x646f72616e646f6d;
-        self.state.v2 = self.k0 ^ 0x6c7967656e657261;
-        self.state.v3 = self.k1 ^ 0x7465646279746573;
+        self.state.v0 = k0 ^ 0x736f6d6570736575;
+        self.state.v1 = k1 ^ 0x646f72616e646f6d;
+        self.state.v2 = k0 ^ 0x6c7967656e657261;
+        self.state.v3 = k1 ^ 0x7465646279746573;
+        self.tail = 0;
         self.ntail = 0;
-        self
+    }
+
+    #[inline]
+    const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.key = SipKey::from_u64s(key0, key1);
+        self.reset_in_place();
+    }
+
+    #[inline]
+    const fn finish_and_reset(&mut self) -> u64 {
+        let h = self.finish();
+        self.reset_in_place();
+        h
     }
 
     // A specialized write function for values with size <= 8.
@@ -390,6 +923,41 @@ impl hash::Hasher for SipHasher {
     fn write_u64(&mut self, i: u64) {
         self.write_u64(i);
     }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
 }
 
 impl SipHasher {
@@ -427,16 +995,146 @@ impl SipHasher {
     pub const fn write_u64(&mut self, i: u64) {
         self.0.write_u64(i);
     }
-}
 
-impl hash::Hasher for SipHasher13 {
     #[inline]
-    fn write(&mut self, msg: &[u8]) {
-        self.write(msg)
+    pub const fn write_u128(&mut self, i: u128) {
+        self.0.write_u128(i);
     }
 
     #[inline]
-    fn finish(&self) -> u64 {
+    pub const fn write_i8(&mut self, i: i8) {
+        self.0.write_i8(i);
+    }
+
+    #[inline]
+    pub const fn write_i16(&mut self, i: i16) {
+        self.0.write_i16(i);
+    }
+
+    #[inline]
+    pub const fn write_i32(&mut self, i: i32) {
+        self.0.write_i32(i);
+    }
+
+    #[inline]
+    pub const fn write_i64(&mut self, i: i64) {
+        self.0.write_i64(i);
+    }
+
+    #[inline]
+    pub const fn write_i128(&mut self, i: i128) {
+        self.0.write_i128(i);
+    }
+
+    #[inline]
+    pub const fn write_isize(&mut self, i: isize) {
+        self.0.write_isize(i);
+    }
+}
+
+impl SipHasher {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.0.write_u16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.0.write_u32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.0.write_u64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.0.write_u128_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.0.write_i16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.0.write_i32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.0.write_i64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.0.write_i128_le(i);
+    }
+
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
+    #[inline]
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.0.write_usize_as_u64(i);
+    }
+
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
+    #[inline]
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.0.write_isize_as_i64(i);
+    }
+
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
+    #[inline]
+    pub const fn write_bool(&mut self, b: bool) {
+        self.0.write_bool(b);
+    }
+
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
+    #[inline]
+    pub const fn write_char(&mut self, c: char) {
+        self.0.write_char(c);
+    }
+
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.0.write_value(value);
+    }
+}
+
+impl hash::Hasher for SipHasher13 {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
         self.finish()
     }
 
@@ -464,6 +1162,41 @@ impl hash::Hasher for SipHasher13 {
     fn write_u64(&mut self, i: u64) {
         self.write_u64(i);
     }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
 }
 
 impl SipHasher13 {
@@ -501,6 +1234,136 @@ impl SipHasher13 {
     pub const fn write_u64(&mut self, i: u64) {
         self.hasher.write_u64(i);
     }
+
+    #[inline]
+    pub const fn write_u128(&mut self, i: u128) {
+        self.hasher.write_u128(i);
+    }
+
+    #[inline]
+    pub const fn write_i8(&mut self, i: i8) {
+        self.hasher.write_i8(i);
+    }
+
+    #[inline]
+    pub const fn write_i16(&mut self, i: i16) {
+        self.hasher.write_i16(i);
+    }
+
+    #[inline]
+    pub const fn write_i32(&mut self, i: i32) {
+        self.hasher.write_i32(i);
+    }
+
+    #[inline]
+    pub const fn write_i64(&mut self, i: i64) {
+        self.hasher.write_i64(i);
+    }
+
+    #[inline]
+    pub const fn write_i128(&mut self, i: i128) {
+        self.hasher.write_i128(i);
+    }
+
+    #[inline]
+    pub const fn write_isize(&mut self, i: isize) {
+        self.hasher.write_isize(i);
+    }
+}
+
+impl SipHasher13 {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.hasher.write_u16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.hasher.write_u32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.hasher.write_u64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.hasher.write_u128_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.hasher.write_i16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.hasher.write_i32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.hasher.write_i64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.hasher.write_i128_le(i);
+    }
+
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
+    #[inline]
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.hasher.write_usize_as_u64(i);
+    }
+
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
+    #[inline]
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.hasher.write_isize_as_i64(i);
+    }
+
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
+    #[inline]
+    pub const fn write_bool(&mut self, b: bool) {
+        self.hasher.write_bool(b);
+    }
+
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
+    #[inline]
+    pub const fn write_char(&mut self, c: char) {
+        self.hasher.write_char(c);
+    }
+
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.hasher.write_value(value);
+    }
 }
 
 impl hash::Hasher for SipHasher24 {
@@ -538,6 +1401,41 @@ impl hash::Hasher for SipHasher24 {
     fn write_u64(&mut self, i: u64) {
         self.write_u64(i);
     }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
 }
 
 impl SipHasher24 {
@@ -575,110 +1473,1150 @@ impl SipHasher24 {
     pub const fn write_u64(&mut self, i: u64) {
         self.hasher.write_u64(i);
     }
-}
 
-impl<S: Sip> Hasher<S> {
     #[inline]
-    const fn write_usize(&mut self, i: usize) {
-        self.short_write(&i, i.to_le() as u64);
+    pub const fn write_u128(&mut self, i: u128) {
+        self.hasher.write_u128(i);
     }
 
     #[inline]
-    const fn write_u8(&mut self, i: u8) {
-        self.short_write(&i, i as u64);
+    pub const fn write_i8(&mut self, i: i8) {
+        self.hasher.write_i8(i);
     }
 
     #[inline]
-    const fn write_u16(&mut self, i: u16) {
-        self.short_write(&i, i.to_le() as u64);
+    pub const fn write_i16(&mut self, i: i16) {
+        self.hasher.write_i16(i);
     }
 
     #[inline]
-    const fn write_u32(&mut self, i: u32) {
-        self.short_write(&i, i.to_le() as u64);
+    pub const fn write_i32(&mut self, i: i32) {
+        self.hasher.write_i32(i);
     }
 
     #[inline]
-    const fn write_u64(&mut self, i: u64) {
-        self.short_write(&i, i.to_le());
+    pub const fn write_i64(&mut self, i: i64) {
+        self.hasher.write_i64(i);
     }
 
     #[inline]
-    const fn write(&mut self, msg: &[u8]) {
-        let length = msg.len();
-        self.length += length;
+    pub const fn write_i128(&mut self, i: i128) {
+        self.hasher.write_i128(i);
+    }
 
-        let mut needed = 0;
+    #[inline]
+    pub const fn write_isize(&mut self, i: isize) {
+        self.hasher.write_isize(i);
+    }
+}
 
-        if self.ntail != 0 {
-            needed = 8 - self.ntail;
-            if length < needed {
-                self.tail |= unsafe { u8to64_le(msg, 0, length) } << (8 * self.ntail);
-                self.ntail += length;
-                return;
-            } else {
-                self.tail |= unsafe { u8to64_le(msg, 0, needed) } << (8 * self.ntail);
-                self.state.v3 ^= self.tail;
-                self.state = c_rounds::<S>(self.state);
-                self.state.v0 ^= self.tail;
-                self.ntail = 0;
-            }
-        }
+impl SipHasher24 {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.hasher.write_u16_le(i);
+    }
 
-        // Buffered tail is now flushed, process new input.
-        let len = length - needed;
-        let left = len & 0x7;
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.hasher.write_u32_le(i);
+    }
 
-        let mut i = needed;
-        while i < len - left {
-            let mi = unsafe { load_int_le!(msg, i, u64) };
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.hasher.write_u64_le(i);
+    }
 
-            self.state.v3 ^= mi;
-            self.state = c_rounds::<S>(self.state);
-            self.state.v0 ^= mi;
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.hasher.write_u128_le(i);
+    }
 
-            i += 8;
-        }
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.hasher.write_i16_le(i);
+    }
 
-        self.tail = unsafe { u8to64_le(msg, i, left) };
-        self.ntail = left;
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.hasher.write_i32_le(i);
     }
 
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    const fn finish(&self) -> u64 {
-        let mut state = self.state;
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.hasher.write_i64_le(i);
+    }
 
-        let b: u64 = ((self.length as u64 & 0xff) << 56) | self.tail;
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.hasher.write_i128_le(i);
+    }
 
-        state.v3 ^= b;
-        state = c_rounds::<S>(state);
-        state.v0 ^= b;
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
+    #[inline]
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.hasher.write_usize_as_u64(i);
+    }
 
-        state.v2 ^= 0xff;
-        state = d_rounds::<S>(state);
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
+    #[inline]
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.hasher.write_isize_as_i64(i);
+    }
 
-        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
+    #[inline]
+    pub const fn write_bool(&mut self, b: bool) {
+        self.hasher.write_bool(b);
     }
-}
 
-impl<S: Sip> Default for Hasher<S> {
-    /// Creates a `Hasher<S>` with the two initial keys set to 0.
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
     #[inline]
-    fn default() -> Hasher<S> {
-        Hasher::new_with_keys(0, 0)
+    pub const fn write_char(&mut self, c: char) {
+        self.hasher.write_char(c);
     }
-}
 
-const fn c_rounds<S: Sip>(mut state: State) -> State {
-    let mut i = 0;
-    while i < S::C_ROUNDS {
-        compress!(state);
-        i += 1;
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.hasher.write_value(value);
     }
-    state
 }
 
-const fn d_rounds<S: Sip>(mut state: State) -> State {
+impl<const C: usize, const D: usize> hash::Hasher for SipHasherCD<C, D> {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write_u64(i);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
+}
+
+impl<const C: usize, const D: usize> SipHasherCD<C, D> {
+    #[inline]
+    pub const fn write(&mut self, msg: &[u8]) {
+        self.hasher.write(msg)
+    }
+
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    #[inline]
+    pub const fn write_usize(&mut self, i: usize) {
+        self.hasher.write_usize(i);
+    }
+
+    #[inline]
+    pub const fn write_u8(&mut self, i: u8) {
+        self.hasher.write_u8(i);
+    }
+
+    #[inline]
+    pub const fn write_u16(&mut self, i: u16) {
+        self.hasher.write_u16(i);
+    }
+
+    #[inline]
+    pub const fn write_u32(&mut self, i: u32) {
+        self.hasher.write_u32(i);
+    }
+
+    #[inline]
+    pub const fn write_u64(&mut self, i: u64) {
+        self.hasher.write_u64(i);
+    }
+
+    #[inline]
+    pub const fn write_u128(&mut self, i: u128) {
+        self.hasher.write_u128(i);
+    }
+
+    #[inline]
+    pub const fn write_i8(&mut self, i: i8) {
+        self.hasher.write_i8(i);
+    }
+
+    #[inline]
+    pub const fn write_i16(&mut self, i: i16) {
+        self.hasher.write_i16(i);
+    }
+
+    #[inline]
+    pub const fn write_i32(&mut self, i: i32) {
+        self.hasher.write_i32(i);
+    }
+
+    #[inline]
+    pub const fn write_i64(&mut self, i: i64) {
+        self.hasher.write_i64(i);
+    }
+
+    #[inline]
+    pub const fn write_i128(&mut self, i: i128) {
+        self.hasher.write_i128(i);
+    }
+
+    #[inline]
+    pub const fn write_isize(&mut self, i: isize) {
+        self.hasher.write_isize(i);
+    }
+}
+
+impl<const C: usize, const D: usize> SipHasherCD<C, D> {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.hasher.write_u16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.hasher.write_u32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.hasher.write_u64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.hasher.write_u128_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.hasher.write_i16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.hasher.write_i32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.hasher.write_i64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.hasher.write_i128_le(i);
+    }
+
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
+    #[inline]
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.hasher.write_usize_as_u64(i);
+    }
+
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
+    #[inline]
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.hasher.write_isize_as_i64(i);
+    }
+
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
+    #[inline]
+    pub const fn write_bool(&mut self, b: bool) {
+        self.hasher.write_bool(b);
+    }
+
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
+    #[inline]
+    pub const fn write_char(&mut self, c: char) {
+        self.hasher.write_char(c);
+    }
+
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.hasher.write_value(value);
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    #[inline]
+    const fn write_usize(&mut self, i: usize) {
+        self.short_write(&i, i.to_le() as u64);
+    }
+
+    #[inline]
+    const fn write_u8(&mut self, i: u8) {
+        self.short_write(&i, i as u64);
+    }
+
+    #[inline]
+    const fn write_u16(&mut self, i: u16) {
+        self.short_write(&i, i.to_le() as u64);
+    }
+
+    #[inline]
+    const fn write_u32(&mut self, i: u32) {
+        self.short_write(&i, i.to_le() as u64);
+    }
+
+    #[inline]
+    const fn write_u64(&mut self, i: u64) {
+        self.short_write(&i, i.to_le());
+    }
+
+    // Hashes the native-endian bytes of `i`, in two 64-bit steps.
+    #[inline]
+    const fn write_u128(&mut self, i: u128) {
+        let (lo, hi) = (i as u64, (i >> 64) as u64);
+        if cfg!(target_endian = "little") {
+            self.write_u64(lo);
+            self.write_u64(hi);
+        } else {
+            self.write_u64(hi);
+            self.write_u64(lo);
+        }
+    }
+
+    #[inline]
+    const fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8);
+    }
+
+    #[inline]
+    const fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    #[inline]
+    const fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    #[inline]
+    const fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    const fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    #[inline]
+    const fn write_isize(&mut self, i: isize) {
+        self.write_usize(i as usize);
+    }
+
+    #[inline]
+    const fn write(&mut self, msg: &[u8]) {
+        let length = msg.len();
+        self.length += length;
+
+        let mut needed = 0;
+
+        if self.ntail != 0 {
+            needed = 8 - self.ntail;
+            if length < needed {
+                self.tail |= unsafe { u8to64_le(msg, 0, length) } << (8 * self.ntail);
+                self.ntail += length;
+                return;
+            } else {
+                self.tail |= unsafe { u8to64_le(msg, 0, needed) } << (8 * self.ntail);
+                self.state.v3 ^= self.tail;
+                self.state = c_rounds::<S>(self.state);
+                self.state.v0 ^= self.tail;
+                self.ntail = 0;
+            }
+        }
+
+        // Buffered tail is now flushed, process new input.
+        let len = length - needed;
+        let left = len & 0x7;
+
+        let mut i = needed;
+        while i < len - left {
+            let mi = unsafe { load_int_le!(msg, i, u64) };
+
+            self.state.v3 ^= mi;
+            self.state = c_rounds::<S>(self.state);
+            self.state.v0 ^= mi;
+
+            i += 8;
+        }
+
+        self.tail = unsafe { u8to64_le(msg, i, left) };
+        self.ntail = left;
+    }
+
+    #[inline]
+    const fn finish(&self) -> u64 {
+        let b: u64 = ((self.length as u64 & 0xff) << 56) | self.tail;
+        finalize::<S>(self.state, b)
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    // One-shot hashing of fixed-size inputs. Unless bytes are buffered in
+    // `tail`, which never happens with a fresh hasher, the input is compressed
+    // in whole blocks and its trailing bytes go straight into the last block.
+    #[inline]
+    const fn hash_u32(mut self, i: u32) -> u64 {
+        if self.ntail != 0 {
+            self.write_u32_le(i);
+            return self.finish();
+        }
+
+        let b: u64 = (((self.length + 4) as u64 & 0xff) << 56) | i as u64;
+        finalize::<S>(self.state, b)
+    }
+
+    #[inline]
+    const fn hash_u64(mut self, i: u64) -> u64 {
+        if self.ntail != 0 {
+            self.write_u64_le(i);
+            return self.finish();
+        }
+
+        self.state.v3 ^= i;
+        self.state = c_rounds::<S>(self.state);
+        self.state.v0 ^= i;
+
+        let b: u64 = ((self.length + 8) as u64 & 0xff) << 56;
+        finalize::<S>(self.state, b)
+    }
+
+    #[inline]
+    const fn hash_u128(mut self, i: u128) -> u64 {
+        if self.ntail != 0 {
+            self.write_u128_le(i);
+            return self.finish();
+        }
+
+        let lo = i as u64;
+        self.state.v3 ^= lo;
+        self.state = c_rounds::<S>(self.state);
+        self.state.v0 ^= lo;
+
+        let hi = (i >> 64) as u64;
+        self.state.v3 ^= hi;
+        self.state = c_rounds::<S>(self.state);
+        self.state.v0 ^= hi;
+
+        let b: u64 = ((self.length + 16) as u64 & 0xff) << 56;
+        finalize::<S>(self.state, b)
+    }
+
+    #[inline]
+    const fn hash_array<const N: usize>(mut self, bytes: &[u8; N]) -> u64 {
+        if self.ntail != 0 {
+            self.write(bytes);
+            return self.finish();
+        }
+
+        let left = N & 0x7;
+        let mut i = 0;
+        while i < N - left {
+            let mi = unsafe { load_int_le!(bytes, i, u64) };
+
+            self.state.v3 ^= mi;
+            self.state = c_rounds::<S>(self.state);
+            self.state.v0 ^= mi;
+
+            i += 8;
+        }
+
+        let b: u64 =
+            (((self.length + N) as u64 & 0xff) << 56) | unsafe { u8to64_le(bytes, i, left) };
+        finalize::<S>(self.state, b)
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    // Hashing of many messages, each as if written after the bytes already
+    // written to the hasher. The lanes start from the current state, which
+    // requires that no bytes are buffered: otherwise every message is hashed
+    // separately.
+    #[inline]
+    const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        assert!(
+            inputs.len() == out.len(),
+            "`inputs` and `out` must have the same length"
+        );
+
+        if self.ntail != 0 {
+            let mut i = 0;
+            while i < inputs.len() {
+                let mut hasher = Hasher::<S> {
+                    key: self.key,
+                    length: self.length,
+                    state: self.state,
+                    tail: self.tail,
+                    ntail: self.ntail,
+                    _marker: PhantomData,
+                };
+                hasher.write(inputs[i]);
+                out[i] = hasher.finish();
+                i += 1;
+            }
+            return;
+        }
+
+        let lanes = Backend::Scalar.lanes();
+        let mut rest = inputs;
+        let mut i = 0;
+        while !rest.is_empty() {
+            let n = if rest.len() < lanes {
+                rest.len()
+            } else {
+                lanes
+            };
+            let (group, tail) = rest.split_at(n);
+            let h = many::hash_group::<S, false>(self.state.to_array(), self.length, group);
+            let mut l = 0;
+            while l < n {
+                out[i + l] = h[l][0];
+                l += 1;
+            }
+            i += n;
+            rest = tail;
+        }
+    }
+
+    #[inline]
+    fn hash_many(&self, inputs: &[&[u8]], out: &mut [u64]) {
+        assert!(
+            inputs.len() == out.len(),
+            "`inputs` and `out` must have the same length"
+        );
+
+        let backend = Backend::detect();
+        if self.ntail != 0 || backend == Backend::Scalar {
+            return self.hash_many_scalar(inputs, out);
+        }
+
+        let lanes = backend.lanes();
+        for (group, out) in inputs.chunks(lanes).zip(out.chunks_mut(lanes)) {
+            let h = backend.hash_group::<S, false>(self.state.to_array(), self.length, group);
+            for (out, h) in out.iter_mut().zip(h) {
+                *out = h[0];
+            }
+        }
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    // Unlike the methods above, the `_le` methods don't byte-swap on
+    // big-endian hardware: `short_write` takes the value whose little-endian
+    // bytes are to be hashed, which is the value itself.
+    #[inline]
+    const fn write_u16_le(&mut self, i: u16) {
+        self.short_write(&i, i as u64);
+    }
+
+    #[inline]
+    const fn write_u32_le(&mut self, i: u32) {
+        self.short_write(&i, i as u64);
+    }
+
+    #[inline]
+    const fn write_u64_le(&mut self, i: u64) {
+        self.short_write(&i, i);
+    }
+
+    #[inline]
+    const fn write_u128_le(&mut self, i: u128) {
+        self.write_u64_le(i as u64);
+        self.write_u64_le((i >> 64) as u64);
+    }
+
+    #[inline]
+    const fn write_i16_le(&mut self, i: i16) {
+        self.write_u16_le(i as u16);
+    }
+
+    #[inline]
+    const fn write_i32_le(&mut self, i: i32) {
+        self.write_u32_le(i as u32);
+    }
+
+    #[inline]
+    const fn write_i64_le(&mut self, i: i64) {
+        self.write_u64_le(i as u64);
+    }
+
+    #[inline]
+    const fn write_i128_le(&mut self, i: i128) {
+        self.write_u128_le(i as u128);
+    }
+
+    #[inline]
+    const fn write_usize_as_u64(&mut self, i: usize) {
+        self.write_u64_le(i as u64);
+    }
+
+    #[inline]
+    const fn write_isize_as_i64(&mut self, i: isize) {
+        self.write_i64_le(i as i64);
+    }
+
+    #[inline]
+    const fn write_bool(&mut self, b: bool) {
+        self.write_u8(b as u8);
+    }
+
+    #[inline]
+    const fn write_char(&mut self, c: char) {
+        self.write_u32_le(c as u32);
+    }
+
+    const fn write_value(&mut self, value: &HashValue<'_>) {
+        match *value {
+            HashValue::U8(i) => self.write_u8(i),
+            HashValue::U16(i) => self.write_u16_le(i),
+            HashValue::U32(i) => self.write_u32_le(i),
+            HashValue::U64(i) => self.write_u64_le(i),
+            HashValue::U128(i) => self.write_u128_le(i),
+            HashValue::Usize(i) => self.write_usize_as_u64(i),
+            HashValue::I8(i) => self.write_u8(i as u8),
+            HashValue::I16(i) => self.write_i16_le(i),
+            HashValue::I32(i) => self.write_i32_le(i),
+            HashValue::I64(i) => self.write_i64_le(i),
+            HashValue::I128(i) => self.write_i128_le(i),
+            HashValue::Isize(i) => self.write_isize_as_i64(i),
+            HashValue::Bool(b) => self.write_bool(b),
+            HashValue::Char(c) => self.write_char(c),
+            HashValue::Str(s) => {
+                self.write_usize_as_u64(s.len());
+                self.write(s.as_bytes());
+            }
+            HashValue::Bytes(bytes) => {
+                self.write_usize_as_u64(bytes.len());
+                self.write(bytes);
+            }
+            HashValue::Slice(items) => {
+                self.write_usize_as_u64(items.len());
+                self.write_values(items);
+            }
+            HashValue::Array(items) | HashValue::Tuple(items) => self.write_values(items),
+        }
+    }
+
+    const fn write_values(&mut self, items: &[HashValue<'_>]) {
+        let mut i = 0;
+        while i < items.len() {
+            self.write_value(&items[i]);
+            i += 1;
+        }
+    }
+}
+
+impl SipBuildHasher13 {
+    /// Creates a new `SipBuildHasher13` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> SipBuildHasher13 {
+        SipBuildHasher13::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipBuildHasher13` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> SipBuildHasher13 {
+        SipBuildHasher13 {
+            hasher: SipHasher13::new_with_keys(key0, key1),
+        }
+    }
+
+    /// Creates a `SipBuildHasher13` from a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipBuildHasher13 {
+        SipBuildHasher13 {
+            hasher: SipHasher13::new_with_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher13` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipBuildHasher13 {
+        SipBuildHasher13 {
+            hasher: SipHasher13::new_with_sip_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher13` keyed off a random key drawn from the
+    /// operating system.
+    #[cfg(feature = "std")]
+    pub fn random() -> SipBuildHasher13 {
+        SipBuildHasher13::new_with_sip_key(SipKey::random())
+    }
+
+    /// Get the keys used by this builder
+    pub const fn keys(&self) -> (u64, u64) {
+        self.hasher.keys()
+    }
+
+    /// Get the key used by this builder as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.sip_key()
+    }
+
+    /// Creates a new hasher keyed off this builder's keys.
+    #[inline]
+    pub const fn build_hasher(&self) -> SipHasher13 {
+        self.hasher
+    }
+}
+
+impl hash::BuildHasher for SipBuildHasher13 {
+    type Hasher = SipHasher13;
+
+    #[inline]
+    fn build_hasher(&self) -> SipHasher13 {
+        self.build_hasher()
+    }
+}
+
+impl SipBuildHasher24 {
+    /// Creates a new `SipBuildHasher24` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> SipBuildHasher24 {
+        SipBuildHasher24::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipBuildHasher24` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> SipBuildHasher24 {
+        SipBuildHasher24 {
+            hasher: SipHasher24::new_with_keys(key0, key1),
+        }
+    }
+
+    /// Creates a `SipBuildHasher24` from a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipBuildHasher24 {
+        SipBuildHasher24 {
+            hasher: SipHasher24::new_with_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher24` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipBuildHasher24 {
+        SipBuildHasher24 {
+            hasher: SipHasher24::new_with_sip_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher24` keyed off a random key drawn from the
+    /// operating system.
+    #[cfg(feature = "std")]
+    pub fn random() -> SipBuildHasher24 {
+        SipBuildHasher24::new_with_sip_key(SipKey::random())
+    }
+
+    /// Get the keys used by this builder
+    pub const fn keys(&self) -> (u64, u64) {
+        self.hasher.keys()
+    }
+
+    /// Get the key used by this builder as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.sip_key()
+    }
+
+    /// Creates a new hasher keyed off this builder's keys.
+    #[inline]
+    pub const fn build_hasher(&self) -> SipHasher24 {
+        self.hasher
+    }
+}
+
+impl hash::BuildHasher for SipBuildHasher24 {
+    type Hasher = SipHasher24;
+
+    #[inline]
+    fn build_hasher(&self) -> SipHasher24 {
+        self.build_hasher()
+    }
+}
+
+impl KeyedSip13 {
+    /// Creates a new `KeyedSip13` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> KeyedSip13 {
+        KeyedSip13::new_with_keys(0, 0)
+    }
+
+    /// Creates a `KeyedSip13` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> KeyedSip13 {
+        KeyedSip13::new_with_sip_key(SipKey::from_u64s(key0, key1))
+    }
+
+    /// Creates a `KeyedSip13` from a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> KeyedSip13 {
+        KeyedSip13::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `KeyedSip13` that is keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> KeyedSip13 {
+        KeyedSip13 {
+            keyed: Keyed::new_with_sip_key(key),
+        }
+    }
+
+    /// Get the keys used by this hasher
+    pub const fn keys(&self) -> (u64, u64) {
+        self.keyed.key.to_u64s()
+    }
+
+    /// Get the key used by this hasher as a 16 byte vector
+    pub const fn key(&self) -> [u8; 16] {
+        self.keyed.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.keyed.key
+    }
+
+    /// Creates an incremental [`SipHasher13`] with the same key.
+    #[inline]
+    pub const fn hasher(&self) -> SipHasher13 {
+        SipHasher13::new_with_sip_key(self.keyed.key)
+    }
+
+    /// Hash a byte array, with the same result as [`SipHasher13::hash`].
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> u64 {
+        self.keyed.hash(bytes)
+    }
+
+    /// Hash a `u64`, with the same result as hashing its little-endian
+    /// bytes. The value is processed as a single block.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> u64 {
+        self.keyed.hash_u64(i)
+    }
+}
+
+impl KeyedSip24 {
+    /// Creates a new `KeyedSip24` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> KeyedSip24 {
+        KeyedSip24::new_with_keys(0, 0)
+    }
+
+    /// Creates a `KeyedSip24` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> KeyedSip24 {
+        KeyedSip24::new_with_sip_key(SipKey::from_u64s(key0, key1))
+    }
+
+    /// Creates a `KeyedSip24` from a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> KeyedSip24 {
+        KeyedSip24::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `KeyedSip24` that is keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> KeyedSip24 {
+        KeyedSip24 {
+            keyed: Keyed::new_with_sip_key(key),
+        }
+    }
+
+    /// Get the keys used by this hasher
+    pub const fn keys(&self) -> (u64, u64) {
+        self.keyed.key.to_u64s()
+    }
+
+    /// Get the key used by this hasher as a 16 byte vector
+    pub const fn key(&self) -> [u8; 16] {
+        self.keyed.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.keyed.key
+    }
+
+    /// Creates an incremental [`SipHasher24`] with the same key.
+    #[inline]
+    pub const fn hasher(&self) -> SipHasher24 {
+        SipHasher24::new_with_sip_key(self.keyed.key)
+    }
+
+    /// Hash a byte array, with the same result as [`SipHasher24::hash`].
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> u64 {
+        self.keyed.hash(bytes)
+    }
+
+    /// Hash a `u64`, with the same result as hashing its little-endian
+    /// bytes. The value is processed as a single block.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> u64 {
+        self.keyed.hash_u64(i)
+    }
+}
+
+impl<S: Sip> Keyed<S> {
+    #[inline]
+    const fn new_with_sip_key(key: SipKey) -> Keyed<S> {
+        let (k0, k1) = key.to_u64s();
+        Keyed {
+            key,
+            state: Hasher::<S>::new_with_keys(k0, k1).state,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    const fn hash(&self, msg: &[u8]) -> u64 {
+        let length = msg.len();
+        let left = length & 0x7;
+        let mut state = self.state;
+
+        let mut i = 0;
+        while i < length - left {
+            let mi = unsafe { load_int_le!(msg, i, u64) };
+
+            state.v3 ^= mi;
+            state = c_rounds::<S>(state);
+            state.v0 ^= mi;
+
+            i += 8;
+        }
+
+        let b: u64 = ((length as u64 & 0xff) << 56) | unsafe { u8to64_le(msg, i, left) };
+        finalize::<S>(state, b)
+    }
+
+    #[inline]
+    const fn hash_u64(&self, i: u64) -> u64 {
+        let mut state = self.state;
+
+        state.v3 ^= i;
+        state = c_rounds::<S>(state);
+        state.v0 ^= i;
+
+        finalize::<S>(state, 8 << 56)
+    }
+}
+
+impl<S: Sip> fmt::Debug for Keyed<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The state is derived from the key, so it isn't printed either.
+        f.debug_struct("Keyed")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Sip> Default for Keyed<S> {
+    /// Creates a `Keyed<S>` with the two initial keys set to 0.
+    #[inline]
+    fn default() -> Keyed<S> {
+        Keyed::new_with_sip_key(SipKey::from_u64s(0, 0))
+    }
+}
+
+impl<S: Sip> fmt::Debug for Hasher<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The state is derived from the key and the tail holds message
+        // bytes, so neither is printed.
+        f.debug_struct("Hasher")
+            .field("key", &self.key)
+            .field("length", &self.length)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Sip> Default for Hasher<S> {
+    /// Creates a `Hasher<S>` with the two initial keys set to 0.
+    #[inline]
+    fn default() -> Hasher<S> {
+        Hasher::new_with_keys(0, 0)
+    }
+}
+
+/// The serialized form of `Hasher<S>`, which keeps the `k0` and `k1` fields
+/// it had before the key moved into a `SipKey`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Hasher", bound = "")]
+struct RawHasher<S: Sip> {
+    k0: u64,
+    k1: u64,
+    length: usize,
+    state: State,
+    tail: u64,
+    ntail: usize,
+    _marker: PhantomData<S>,
+}
+
+#[cfg(feature = "serde")]
+impl<S: Sip> From<Hasher<S>> for RawHasher<S> {
+    fn from(hasher: Hasher<S>) -> RawHasher<S> {
+        let (k0, k1) = hasher.key.to_u64s();
+        RawHasher {
+            k0,
+            k1,
+            length: hasher.length,
+            state: hasher.state,
+            tail: hasher.tail,
+            ntail: hasher.ntail,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: Sip> From<Keyed<S>> for SipKey {
+    fn from(keyed: Keyed<S>) -> SipKey {
+        keyed.key
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: Sip> From<SipKey> for Keyed<S> {
+    fn from(key: SipKey) -> Keyed<S> {
+        Keyed::new_with_sip_key(key)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: Sip> From<RawHasher<S>> for Hasher<S> {
+    fn from(raw: RawHasher<S>) -> Hasher<S> {
+        Hasher {
+            key: SipKey::from_u64s(raw.k0, raw.k1),
+            length: raw.length,
+            state: raw.state,
+            tail: raw.tail,
+            ntail: raw.ntail,
+            _marker: PhantomData,
+        }
+    }
+}
+
+const fn c_rounds<S: Sip>(mut state: State) -> State {
+    let mut i = 0;
+    while i < S::C_ROUNDS {
+        compress!(state);
+        i += 1;
+    }
+    state
+}
+
+const fn d_rounds<S: Sip>(mut state: State) -> State {
     let mut i = 0;
     while i < S::D_ROUNDS {
         compress!(state);
@@ -686,3 +2624,377 @@ const fn d_rounds<S: Sip>(mut state: State) -> State {
     }
     state
 }
+
+/// Processes the last block `b`, holding the message length and its trailing
+/// bytes, and returns the hash.
+const fn finalize<S: Sip>(mut state: State, b: u64) -> u64 {
+    state.v3 ^= b;
+    state = c_rounds::<S>(state);
+    state.v0 ^= b;
+
+    state.v2 ^= 0xff;
+    state = d_rounds::<S>(state);
+
+    state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for SipHasher {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl digest::crypto_common::KeySizeUser for SipHasher {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::KeyInit for SipHasher {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for SipHasher {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for SipHasher {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for SipHasher {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutputReset for SipHasher {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::MacMarker for SipHasher {}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for SipHasher13 {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl digest::crypto_common::KeySizeUser for SipHasher13 {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::KeyInit for SipHasher13 {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for SipHasher13 {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for SipHasher13 {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for SipHasher13 {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutputReset for SipHasher13 {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::MacMarker for SipHasher13 {}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for SipHasher24 {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl digest::crypto_common::KeySizeUser for SipHasher24 {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::KeyInit for SipHasher24 {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for SipHasher24 {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for SipHasher24 {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for SipHasher24 {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutputReset for SipHasher24 {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::MacMarker for SipHasher24 {}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::OutputSizeUser for SipHasherCD<C, D> {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::crypto_common::KeySizeUser for SipHasherCD<C, D> {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::KeyInit for SipHasherCD<C, D> {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::Update for SipHasherCD<C, D> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::FixedOutput for SipHasherCD<C, D> {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::Reset for SipHasherCD<C, D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::FixedOutputReset for SipHasherCD<C, D> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish().to_le_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::MacMarker for SipHasherCD<C, D> {}
+
+#[cfg(feature = "std")]
+impl std::io::Write for SipHasher {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for SipHasher13 {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for SipHasher24 {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const C: usize, const D: usize> std::io::Write for SipHasherCD<C, D> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Sip> zeroize::Zeroize for Hasher<S> {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.length.zeroize();
+        self.state.v0.zeroize();
+        self.state.v2.zeroize();
+        self.state.v1.zeroize();
+        self.state.v3.zeroize();
+        self.tail.zeroize();
+        self.ntail.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipHasher {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipHasher13 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipHasher24 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const C: usize, const D: usize> zeroize::Zeroize for SipHasherCD<C, D> {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipBuildHasher13 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipBuildHasher24 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Sip> zeroize::Zeroize for Keyed<S> {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.state.v0.zeroize();
+        self.state.v2.zeroize();
+        self.state.v1.zeroize();
+        self.state.v3.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for KeyedSip13 {
+    fn zeroize(&mut self) {
+        self.keyed.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for KeyedSip24 {
+    fn zeroize(&mut self) {
+        self.keyed.zeroize();
+    }
+}
@@ -10,21 +10,50 @@
 
 //! An implementation of SipHash with a 128-bit output.
 
+use core::convert::TryFrom;
+use core::fmt;
 use core::hash;
 use core::marker::PhantomData;
 use core::mem;
 use core::ptr;
+use core::str::FromStr;
 
-use crate::{Sip, Sip13Rounds, Sip24Rounds};
+use crate::many::{self, Backend};
+use crate::{HashValue, Sip, Sip13Rounds, Sip24Rounds, SipCDRounds, SipKey};
 
 /// A 128-bit (2x64) hash output
-#[derive(Debug, Clone, Copy, Default)]
+///
+/// The `Display`, `LowerHex` and `UpperHex` implementations print the 32
+/// hexadecimal digits of [`as_bytes`](Hash128::as_bytes), and `FromStr`
+/// parses that form back.
+///
+/// Hashes are ordered by their [`as_u128`](Hash128::as_u128) value, `h2`
+/// being the high half. This is not the order of the hexadecimal strings,
+/// which start with the low byte.
+///
+/// `==` is not constant-time: use [`ct_eq`](Hash128::ct_eq) to compare
+/// authentication tags.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hash128 {
     pub h1: u64,
     pub h2: u64,
 }
 
+impl PartialOrd for Hash128 {
+    #[inline]
+    fn partial_cmp(&self, other: &Hash128) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hash128 {
+    #[inline]
+    fn cmp(&self, other: &Hash128) -> core::cmp::Ordering {
+        self.as_u128().cmp(&other.as_u128())
+    }
+}
+
 impl From<u128> for Hash128 {
     fn from(v: u128) -> Self {
         Self::from_u128(v)
@@ -33,10 +62,155 @@ impl From<u128> for Hash128 {
 
 impl From<Hash128> for u128 {
     fn from(h: Hash128) -> u128 {
-        (h.h1 as u128) | ((h.h2 as u128) << 64)
+        h.as_u128()
+    }
+}
+
+impl fmt::Display for Hash128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for Hash128 {
+    /// Like the integer types, honors the width, fill and alignment, the `0`
+    /// flag, and prints a `0x` prefix with the `#` flag.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = hex_digits(&self.as_bytes(), b"0123456789abcdef");
+        f.pad_integral(true, "0x", hex_str(&digits))
+    }
+}
+
+impl fmt::UpperHex for Hash128 {
+    /// Like the integer types, honors the width, fill and alignment, the `0`
+    /// flag, and prints a `0x` prefix with the `#` flag.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = hex_digits(&self.as_bytes(), b"0123456789ABCDEF");
+        f.pad_integral(true, "0x", hex_str(&digits))
+    }
+}
+
+impl FromStr for Hash128 {
+    type Err = ParseHash128Error;
+
+    /// Parses the 32 hexadecimal digits printed by the `Display`
+    /// implementation, in either case, with an optional `0x` prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.as_bytes();
+        let s = match s {
+            [b'0', b'x' | b'X', digits @ ..] => digits,
+            _ => s,
+        };
+        if s.len() != 32 {
+            return Err(ParseHash128Error {
+                kind: ParseHash128ErrorKind::InvalidLength,
+            });
+        }
+        let mut bytes = [0u8; 16];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            let hi = hex_digit(s[2 * i]);
+            let lo = hex_digit(s[2 * i + 1]);
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => *b = (hi << 4) | lo,
+                _ => {
+                    return Err(ParseHash128Error {
+                        kind: ParseHash128ErrorKind::InvalidDigit,
+                    })
+                }
+            }
+        }
+        Ok(Hash128::from_bytes(&bytes))
+    }
+}
+
+impl TryFrom<&[u8]> for Hash128 {
+    type Error = Hash128LengthError;
+
+    /// Converts a 16 byte slice, in the [`as_bytes`](Hash128::as_bytes)
+    /// form, into a [`Hash128`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        match <&[u8; 16]>::try_from(bytes) {
+            Ok(bytes) => Ok(Hash128::from_bytes(bytes)),
+            Err(_) => Err(Hash128LengthError { len: bytes.len() }),
+        }
+    }
+}
+
+/// Returns the 32 hexadecimal digits of `bytes`, in order.
+const fn hex_digits(bytes: &[u8; 16], alphabet: &[u8; 16]) -> [u8; 32] {
+    let mut digits = [0u8; 32];
+    let mut i = 0;
+    while i < 16 {
+        digits[2 * i] = alphabet[(bytes[i] >> 4) as usize];
+        digits[2 * i + 1] = alphabet[(bytes[i] & 0xf) as usize];
+        i += 1;
+    }
+    digits
+}
+
+fn hex_str(digits: &[u8; 32]) -> &str {
+    // The digits all come from an ASCII alphabet.
+    core::str::from_utf8(digits).unwrap()
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// An error returned when parsing a [`Hash128`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHash128Error {
+    kind: ParseHash128ErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseHash128ErrorKind {
+    InvalidLength,
+    InvalidDigit,
+}
+
+impl fmt::Display for ParseHash128Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseHash128ErrorKind::InvalidLength => {
+                f.write_str("a 128-bit hash must be 32 hexadecimal digits long")
+            }
+            ParseHash128ErrorKind::InvalidDigit => f.write_str("invalid hexadecimal digit"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseHash128Error {}
+
+/// An error returned when converting a slice that isn't 16 bytes long into a
+/// [`Hash128`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hash128LengthError {
+    len: usize,
+}
+
+impl Hash128LengthError {
+    /// The length of the slice that failed to convert.
+    pub const fn slice_len(&self) -> usize {
+        self.len
     }
 }
 
+impl fmt::Display for Hash128LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a 128-bit hash must be 16 bytes long, got {}", self.len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Hash128LengthError {}
+
 /// An implementation of SipHash128 1-3.
 #[derive(Debug, Clone, Copy, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -51,6 +225,18 @@ pub struct SipHasher24 {
     hasher: Hasher<Sip24Rounds>,
 }
 
+/// An implementation of SipHash128 C-D, with a caller-chosen number of
+/// compression (`C`) and finalization (`D`) rounds.
+///
+/// `SipHasherCD<1, 3>` and `SipHasherCD<2, 4>` are equivalent to
+/// [`SipHasher13`] and [`SipHasher24`]. `SipHasherCD<4, 8>` is the
+/// conservative variant suggested in the paper.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipHasherCD<const C: usize, const D: usize> {
+    hasher: Hasher<SipCDRounds<C, D>>,
+}
+
 /// An implementation of SipHash128 2-4.
 ///
 /// SipHash is a general-purpose hashing function: it runs at a good
@@ -64,11 +250,38 @@ pub struct SipHasher24 {
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SipHasher(SipHasher24);
 
-#[derive(Debug, Copy)]
+/// A [`BuildHasher`](hash::BuildHasher) creating [`SipHasher13`] instances
+/// keyed off a fixed key.
+///
+/// This can be used to plug SipHash128 1-3 into `HashMap` and `HashSet`.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipBuildHasher13 {
+    hasher: SipHasher13,
+}
+
+/// A [`BuildHasher`](hash::BuildHasher) creating [`SipHasher24`] instances
+/// keyed off a fixed key.
+///
+/// This can be used to plug SipHash128 2-4 into `HashMap` and `HashSet`.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SipBuildHasher24 {
+    hasher: SipHasher24,
+}
+
+#[derive(Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        into = "RawHasher<S>",
+        from = "RawHasher<S>",
+        bound(serialize = "S: Clone", deserialize = "")
+    )
+)]
 struct Hasher<S: Sip> {
-    k0: u64,
-    k1: u64,
+    key: SipKey,
     length: usize, // how many bytes we've processed
     state: State,  // hash State
     tail: u64,     // unprocessed bytes le
@@ -89,6 +302,14 @@ struct State {
     v3: u64,
 }
 
+impl State {
+    /// Returns `[v0, v1, v2, v3]`.
+    #[inline]
+    const fn to_array(self) -> [u64; 4] {
+        [self.v0, self.v1, self.v2, self.v3]
+    }
+}
+
 macro_rules! compress {
     ($state:expr) => {{
         compress!($state.v0, $state.v1, $state.v2, $state.v3)
@@ -174,27 +395,31 @@ impl SipHasher {
     }
 
     /// Creates a `SipHasher` from a 16 byte key.
-    pub fn new_with_key(key: &[u8; 16]) -> SipHasher {
-        let mut b0 = [0u8; 8];
-        let mut b1 = [0u8; 8];
-        b0.copy_from_slice(&key[0..8]);
-        b1.copy_from_slice(&key[8..16]);
-        let key0 = u64::from_le_bytes(b0);
-        let key1 = u64::from_le_bytes(b1);
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipHasher {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasher` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipHasher {
+        let (key0, key1) = key.to_u64s();
         Self::new_with_keys(key0, key1)
     }
 
     /// Get the keys used by this hasher
     pub const fn keys(&self) -> (u64, u64) {
-        (self.0.hasher.k0, self.0.hasher.k1)
+        self.0.hasher.key.to_u64s()
     }
 
     /// Get the key used by this hasher as a 16 byte vector
-    pub fn key(&self) -> [u8; 16] {
-        let mut bytes = [0u8; 16];
-        bytes[0..8].copy_from_slice(&self.0.hasher.k0.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.0.hasher.k1.to_le_bytes());
-        bytes
+    pub const fn key(&self) -> [u8; 16] {
+        self.0.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.0.hasher.key
     }
 
     /// Hash a byte array - This is the easiest and safest way to use SipHash.
@@ -204,6 +429,103 @@ impl SipHasher {
         hasher.write(bytes);
         hasher.finish128()
     }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> Hash128 {
+        let mut hasher = self.0.hasher;
+        hasher.write_value(value);
+        hasher.finish128()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> Hash128 {
+        self.0.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> Hash128 {
+        self.0.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> Hash128 {
+        self.0.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> Hash128 {
+        self.0.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.0.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.0.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the output of [`hash`](Self::hash) for `msg`, in the
+    /// [`Hash128::as_bytes`] form.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 16]) -> bool {
+        crate::ct_eq(&self.hash(msg).as_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.0.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.0.finish_and_reset()
+    }
+
+    /// Returns the 128-bit hash of the data written so far, and resets the
+    /// hasher.
+    #[inline]
+    pub const fn finish128_and_reset(&mut self) -> Hash128 {
+        self.0.finish128_and_reset()
+    }
 }
 
 impl Hasher128 for SipHasher {
@@ -237,27 +559,31 @@ impl SipHasher13 {
     }
 
     /// Creates a `SipHasher13` from a 16 byte key.
-    pub fn new_with_key(key: &[u8; 16]) -> SipHasher13 {
-        let mut b0 = [0u8; 8];
-        let mut b1 = [0u8; 8];
-        b0.copy_from_slice(&key[0..8]);
-        b1.copy_from_slice(&key[8..16]);
-        let key0 = u64::from_le_bytes(b0);
-        let key1 = u64::from_le_bytes(b1);
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipHasher13 {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasher13` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipHasher13 {
+        let (key0, key1) = key.to_u64s();
         Self::new_with_keys(key0, key1)
     }
 
     /// Get the keys used by this hasher
     pub const fn keys(&self) -> (u64, u64) {
-        (self.hasher.k0, self.hasher.k1)
+        self.hasher.key.to_u64s()
     }
 
     /// Get the key used by this hasher as a 16 byte vector
-    pub fn key(&self) -> [u8; 16] {
-        let mut bytes = [0u8; 16];
-        bytes[0..8].copy_from_slice(&self.hasher.k0.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.hasher.k1.to_le_bytes());
-        bytes
+    pub const fn key(&self) -> [u8; 16] {
+        self.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.key
     }
 
     /// Hash a byte array - This is the easiest and safest way to use SipHash.
@@ -267,6 +593,103 @@ impl SipHasher13 {
         hasher.write(bytes);
         hasher.finish128()
     }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> Hash128 {
+        let mut hasher = self.hasher;
+        hasher.write_value(value);
+        hasher.finish128()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> Hash128 {
+        self.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> Hash128 {
+        self.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> Hash128 {
+        self.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> Hash128 {
+        self.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the output of [`hash`](Self::hash) for `msg`, in the
+    /// [`Hash128::as_bytes`] form.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 16]) -> bool {
+        crate::ct_eq(&self.hash(msg).as_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.hasher.reset_in_place();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.hasher.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.hasher.finish_and_reset()
+    }
+
+    /// Returns the 128-bit hash of the data written so far, and resets the
+    /// hasher.
+    #[inline]
+    pub const fn finish128_and_reset(&mut self) -> Hash128 {
+        self.hasher.finish128_and_reset()
+    }
 }
 
 impl Hasher128 for SipHasher13 {
@@ -300,27 +723,31 @@ impl SipHasher24 {
     }
 
     /// Creates a `SipHasher24` from a 16 byte key.
-    pub fn new_with_key(key: &[u8; 16]) -> SipHasher24 {
-        let mut b0 = [0u8; 8];
-        let mut b1 = [0u8; 8];
-        b0.copy_from_slice(&key[0..8]);
-        b1.copy_from_slice(&key[8..16]);
-        let key0 = u64::from_le_bytes(b0);
-        let key1 = u64::from_le_bytes(b1);
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipHasher24 {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasher24` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipHasher24 {
+        let (key0, key1) = key.to_u64s();
         Self::new_with_keys(key0, key1)
     }
 
     /// Get the keys used by this hasher
     pub const fn keys(&self) -> (u64, u64) {
-        (self.hasher.k0, self.hasher.k1)
+        self.hasher.key.to_u64s()
     }
 
     /// Get the key used by this hasher as a 16 byte vector
-    pub fn key(&self) -> [u8; 16] {
-        let mut bytes = [0u8; 16];
-        bytes[0..8].copy_from_slice(&self.hasher.k0.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.hasher.k1.to_le_bytes());
-        bytes
+    pub const fn key(&self) -> [u8; 16] {
+        self.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.key
     }
 
     /// Hash a byte array - This is the easiest and safest way to use SipHash.
@@ -330,6 +757,103 @@ impl SipHasher24 {
         hasher.write(bytes);
         hasher.finish128()
     }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> Hash128 {
+        let mut hasher = self.hasher;
+        hasher.write_value(value);
+        hasher.finish128()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> Hash128 {
+        self.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> Hash128 {
+        self.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> Hash128 {
+        self.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> Hash128 {
+        self.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the output of [`hash`](Self::hash) for `msg`, in the
+    /// [`Hash128::as_bytes`] form.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 16]) -> bool {
+        crate::ct_eq(&self.hash(msg).as_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.hasher.reset_in_place();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.hasher.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.hasher.finish_and_reset()
+    }
+
+    /// Returns the 128-bit hash of the data written so far, and resets the
+    /// hasher.
+    #[inline]
+    pub const fn finish128_and_reset(&mut self) -> Hash128 {
+        self.hasher.finish128_and_reset()
+    }
 }
 
 impl Hasher128 for SipHasher24 {
@@ -348,53 +872,244 @@ impl SipHasher24 {
     }
 }
 
-impl<S: Sip> Hasher<S> {
+impl<const C: usize, const D: usize> SipHasherCD<C, D> {
+    /// Creates a new `SipHasherCD` with the two initial keys set to 0.
     #[inline]
-    const fn new_with_keys(key0: u64, key1: u64) -> Hasher<S> {
-        let mut state = Hasher {
-            k0: key0,
-            k1: key1,
-            length: 0,
-            state: State {
-                v0: 0,
-                v1: 0xee,
-                v2: 0,
-                v3: 0,
-            },
-            tail: 0,
-            ntail: 0,
-            _marker: PhantomData,
-        };
-        state = state.reset();
-        state
+    pub const fn new() -> Self {
+        Self::new_with_keys(0, 0)
     }
 
+    /// Creates a `SipHasherCD` that is keyed off the provided keys.
     #[inline]
-    #[must_use]
-    const fn reset(mut self) -> Self {
-        self.length = 0;
-        self.state.v0 = self.k0 ^ 0x736f6d6570736575;
-        self.state.v1 = self.k1 ^ 0x646f72616e646f83;
-        self.state.v2 = self.k0 ^ 0x6c7967656e657261;
-        self.state.v3 = self.k1 ^ 0x7465646279746573;
-        self.ntail = 0;
-        self
+    pub const fn new_with_keys(key0: u64, key1: u64) -> Self {
+        SipHasherCD {
+            hasher: Hasher::new_with_keys(key0, key1),
+        }
     }
 
-    // A specialized write function for values with size <= 8.
-    //
-    // The hashing of multi-byte integers depends on endianness. E.g.:
-    // - little-endian: `write_u32(0xDDCCBBAA)` == `write([0xAA, 0xBB, 0xCC, 0xDD])`
-    // - big-endian:    `write_u32(0xDDCCBBAA)` == `write([0xDD, 0xCC, 0xBB, 0xAA])`
-    //
-    // This function does the right thing for little-endian hardware. On
-    // big-endian hardware `x` must be byte-swapped first to give the right
-    // behaviour. After any byte-swapping, the input must be zero-extended to
-    // 64-bits. The caller is responsible for the byte-swapping and
-    // zero-extension.
+    /// Creates a `SipHasherCD` from a 16 byte key.
     #[inline]
-    const fn short_write<T>(&mut self, _x: &T, x: u64) {
-        let size = mem::size_of::<T>();
+    pub const fn new_with_key(key: &[u8; 16]) -> Self {
+        Self::new_with_sip_key(SipKey::from_key_bytes(key))
+    }
+
+    /// Creates a `SipHasherCD` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> Self {
+        let (key0, key1) = key.to_u64s();
+        Self::new_with_keys(key0, key1)
+    }
+
+    /// Get the keys used by this hasher
+    pub const fn keys(&self) -> (u64, u64) {
+        self.hasher.key.to_u64s()
+    }
+
+    /// Get the key used by this hasher as a 16 byte vector
+    pub const fn key(&self) -> [u8; 16] {
+        self.hasher.key.to_key_bytes()
+    }
+
+    /// Get the key used by this hasher as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.key
+    }
+
+    /// Hash a byte array - This is the easiest and safest way to use SipHash.
+    #[inline]
+    pub const fn hash(&self, bytes: &[u8]) -> Hash128 {
+        let mut hasher = self.hasher;
+        hasher.write(bytes);
+        hasher.finish128()
+    }
+
+    /// Hash a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn hash_value(&self, value: &HashValue<'_>) -> Hash128 {
+        let mut hasher = self.hasher;
+        hasher.write_value(value);
+        hasher.finish128()
+    }
+
+    /// Hash a `u32`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u32(&self, i: u32) -> Hash128 {
+        self.hasher.hash_u32(i)
+    }
+
+    /// Hash a `u64`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u64(&self, i: u64) -> Hash128 {
+        self.hasher.hash_u64(i)
+    }
+
+    /// Hash a `u128`, with the same result as [`hash`](Self::hash) on its
+    /// little-endian bytes, but without the generic byte loop.
+    #[inline]
+    pub const fn hash_u128(&self, i: u128) -> Hash128 {
+        self.hasher.hash_u128(i)
+    }
+
+    /// Hash a fixed-size byte array, with the same result as
+    /// [`hash`](Self::hash). The length being known, the loop over its blocks
+    /// can be unrolled.
+    #[inline]
+    pub const fn hash_array<const N: usize>(&self, bytes: &[u8; N]) -> Hash128 {
+        self.hasher.hash_array(bytes)
+    }
+
+    /// Hashes every message of `inputs` into the matching element of `out`,
+    /// with the same results as [`hash`](Self::hash).
+    ///
+    /// The messages are hashed several at a time, in the lanes of SIMD vectors
+    /// when the CPU supports it: SSE2 or AVX2 on x86, detected at runtime with
+    /// `std`, and NEON on AArch64.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub fn hash_many(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.hasher.hash_many(inputs, out);
+    }
+
+    /// Like [`hash_many`](Self::hash_many), but usable in `const` contexts: the
+    /// messages are hashed by interleaved scalar code.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` and `out` don't have the same length.
+    #[inline]
+    pub const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        self.hasher.hash_many_scalar(inputs, out);
+    }
+
+    /// Checks that `tag` is the output of [`hash`](Self::hash) for `msg`, in the
+    /// [`Hash128::as_bytes`] form.
+    ///
+    /// The comparison takes the same time wherever the tags differ.
+    #[inline]
+    pub const fn verify(&self, msg: &[u8], tag: &[u8; 16]) -> bool {
+        crate::ct_eq(&self.hash(msg).as_bytes(), tag)
+    }
+
+    /// Resets the hasher to its initial state, keeping its keys.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.hasher.reset_in_place();
+    }
+
+    /// Resets the hasher to its initial state, keyed off the provided keys.
+    #[inline]
+    pub const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.hasher.rekey(key0, key1);
+    }
+
+    /// Returns the hash of the data written so far, and resets the hasher.
+    #[inline]
+    pub const fn finish_and_reset(&mut self) -> u64 {
+        self.hasher.finish_and_reset()
+    }
+
+    /// Returns the 128-bit hash of the data written so far, and resets the
+    /// hasher.
+    #[inline]
+    pub const fn finish128_and_reset(&mut self) -> Hash128 {
+        self.hasher.finish128_and_reset()
+    }
+}
+
+impl<const C: usize, const D: usize> Hasher128 for SipHasherCD<C, D> {
+    /// Return a 128-bit hash
+    #[inline]
+    fn finish128(&self) -> Hash128 {
+        self.finish128()
+    }
+}
+
+impl<const C: usize, const D: usize> SipHasherCD<C, D> {
+    /// Return a 128-bit hash
+    #[inline]
+    pub const fn finish128(&self) -> Hash128 {
+        self.hasher.finish128()
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    #[inline]
+    const fn new_with_keys(key0: u64, key1: u64) -> Hasher<S> {
+        let mut state = Hasher {
+            key: SipKey::from_u64s(key0, key1),
+            length: 0,
+            state: State {
+                v0: 0,
+                v1: 0xee,
+                v2: 0,
+                v3: 0,
+            },
+            tail: 0,
+            ntail: 0,
+            _marker: PhantomData,
+        };
+        state = state.reset();
+        state
+    }
+
+    #[inline]
+    #[must_use]
+    const fn reset(mut self) -> Self {
+        self.reset_in_place();
+        self
+    }
+
+    #[inline]
+    const fn reset_in_place(&mut self) {
+        let (k0, k1) = self.key.to_u64s();
+        self.length = 0;
+        self.state.v0 = k0 ^ 0x736f6d6570736575;
+        self.state.v1 = k1 ^ 0x646f72616e646f83;
+        self.state.v2 = k0 ^ 0x6c7967656e657261;
+        self.state.v3 = k1 ^ 0x7465646279746573;
+        self.tail = 0;
+        self.ntail = 0;
+    }
+
+    #[inline]
+    const fn rekey(&mut self, key0: u64, key1: u64) {
+        self.key = SipKey::from_u64s(key0, key1);
+        self.reset_in_place();
+    }
+
+    #[inline]
+    const fn finish_and_reset(&mut self) -> u64 {
+        let h = self.finish();
+        self.reset_in_place();
+        h
+    }
+
+    #[inline]
+    const fn finish128_and_reset(&mut self) -> Hash128 {
+        let h = self.finish128();
+        self.reset_in_place();
+        h
+    }
+
+    // A specialized write function for values with size <= 8.
+    //
+    // The hashing of multi-byte integers depends on endianness. E.g.:
+    // - little-endian: `write_u32(0xDDCCBBAA)` == `write([0xAA, 0xBB, 0xCC, 0xDD])`
+    // - big-endian:    `write_u32(0xDDCCBBAA)` == `write([0xDD, 0xCC, 0xBB, 0xAA])`
+    //
+    // This function does the right thing for little-endian hardware. On
+    // big-endian hardware `x` must be byte-swapped first to give the right
+    // behaviour. After any byte-swapping, the input must be zero-extended to
+    // 64-bits. The caller is responsible for the byte-swapping and
+    // zero-extension.
+    #[inline]
+    const fn short_write<T>(&mut self, _x: &T, x: u64) {
+        let size = mem::size_of::<T>();
         self.length += size;
 
         // The original number must be zero-extended, not sign-extended.
@@ -422,23 +1137,160 @@ impl<S: Sip> Hasher<S> {
 impl<S: Sip> Hasher<S> {
     #[inline]
     pub const fn finish128(&self) -> Hash128 {
-        let mut state = self.state;
-
         let b: u64 = ((self.length as u64 & 0xff) << 56) | self.tail;
+        finalize128::<S>(self.state, b)
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    // One-shot hashing of fixed-size inputs. Unless bytes are buffered in
+    // `tail`, which never happens with a fresh hasher, the input is compressed
+    // in whole blocks and its trailing bytes go straight into the last block.
+    #[inline]
+    const fn hash_u32(mut self, i: u32) -> Hash128 {
+        if self.ntail != 0 {
+            self.write_u32_le(i);
+            return self.finish128();
+        }
 
-        state.v3 ^= b;
-        state = c_rounds::<S>(state);
-        state.v0 ^= b;
+        let b: u64 = (((self.length + 4) as u64 & 0xff) << 56) | i as u64;
+        finalize128::<S>(self.state, b)
+    }
 
-        state.v2 ^= 0xee;
-        state = d_rounds::<S>(state);
-        let h1 = state.v0 ^ state.v1 ^ state.v2 ^ state.v3;
+    #[inline]
+    const fn hash_u64(mut self, i: u64) -> Hash128 {
+        if self.ntail != 0 {
+            self.write_u64_le(i);
+            return self.finish128();
+        }
 
-        state.v1 ^= 0xdd;
-        state = d_rounds::<S>(state);
-        let h2 = state.v0 ^ state.v1 ^ state.v2 ^ state.v3;
+        self.state.v3 ^= i;
+        self.state = c_rounds::<S>(self.state);
+        self.state.v0 ^= i;
 
-        Hash128 { h1, h2 }
+        let b: u64 = ((self.length + 8) as u64 & 0xff) << 56;
+        finalize128::<S>(self.state, b)
+    }
+
+    #[inline]
+    const fn hash_u128(mut self, i: u128) -> Hash128 {
+        if self.ntail != 0 {
+            self.write_u128_le(i);
+            return self.finish128();
+        }
+
+        let lo = i as u64;
+        self.state.v3 ^= lo;
+        self.state = c_rounds::<S>(self.state);
+        self.state.v0 ^= lo;
+
+        let hi = (i >> 64) as u64;
+        self.state.v3 ^= hi;
+        self.state = c_rounds::<S>(self.state);
+        self.state.v0 ^= hi;
+
+        let b: u64 = ((self.length + 16) as u64 & 0xff) << 56;
+        finalize128::<S>(self.state, b)
+    }
+
+    #[inline]
+    const fn hash_array<const N: usize>(mut self, bytes: &[u8; N]) -> Hash128 {
+        if self.ntail != 0 {
+            self.write(bytes);
+            return self.finish128();
+        }
+
+        let left = N & 0x7;
+        let mut i = 0;
+        while i < N - left {
+            let mi = unsafe { load_int_le!(bytes, i, u64) };
+
+            self.state.v3 ^= mi;
+            self.state = c_rounds::<S>(self.state);
+            self.state.v0 ^= mi;
+
+            i += 8;
+        }
+
+        let b: u64 =
+            (((self.length + N) as u64 & 0xff) << 56) | unsafe { u8to64_le(bytes, i, left) };
+        finalize128::<S>(self.state, b)
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    // Hashing of many messages, each as if written after the bytes already
+    // written to the hasher. The lanes start from the current state, which
+    // requires that no bytes are buffered: otherwise every message is hashed
+    // separately.
+    #[inline]
+    const fn hash_many_scalar(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        assert!(
+            inputs.len() == out.len(),
+            "`inputs` and `out` must have the same length"
+        );
+
+        if self.ntail != 0 {
+            let mut i = 0;
+            while i < inputs.len() {
+                let mut hasher = Hasher::<S> {
+                    key: self.key,
+                    length: self.length,
+                    state: self.state,
+                    tail: self.tail,
+                    ntail: self.ntail,
+                    _marker: PhantomData,
+                };
+                hasher.write(inputs[i]);
+                out[i] = hasher.finish128();
+                i += 1;
+            }
+            return;
+        }
+
+        let lanes = Backend::Scalar.lanes();
+        let mut rest = inputs;
+        let mut i = 0;
+        while !rest.is_empty() {
+            let n = if rest.len() < lanes {
+                rest.len()
+            } else {
+                lanes
+            };
+            let (group, tail) = rest.split_at(n);
+            let h = many::hash_group::<S, true>(self.state.to_array(), self.length, group);
+            let mut l = 0;
+            while l < n {
+                out[i + l] = Hash128 {
+                    h1: h[l][0],
+                    h2: h[l][1],
+                };
+                l += 1;
+            }
+            i += n;
+            rest = tail;
+        }
+    }
+
+    #[inline]
+    fn hash_many(&self, inputs: &[&[u8]], out: &mut [Hash128]) {
+        assert!(
+            inputs.len() == out.len(),
+            "`inputs` and `out` must have the same length"
+        );
+
+        let backend = Backend::detect();
+        if self.ntail != 0 || backend == Backend::Scalar {
+            return self.hash_many_scalar(inputs, out);
+        }
+
+        let lanes = backend.lanes();
+        for (group, out) in inputs.chunks(lanes).zip(out.chunks_mut(lanes)) {
+            let h = backend.hash_group::<S, true>(self.state.to_array(), self.length, group);
+            for (out, h) in out.iter_mut().zip(h) {
+                *out = Hash128 { h1: h[0], h2: h[1] };
+            }
+        }
     }
 }
 
@@ -477,6 +1329,41 @@ impl hash::Hasher for SipHasher {
     fn write_u64(&mut self, i: u64) {
         self.write_u64(i);
     }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
 }
 
 impl SipHasher {
@@ -514,110 +1401,166 @@ impl SipHasher {
     pub const fn write_u64(&mut self, i: u64) {
         self.0.write_u64(i);
     }
-}
 
-impl hash::Hasher for SipHasher13 {
     #[inline]
-    fn write(&mut self, msg: &[u8]) {
-        self.write(msg)
+    pub const fn write_u128(&mut self, i: u128) {
+        self.0.write_u128(i);
     }
 
     #[inline]
-    fn finish(&self) -> u64 {
-        self.finish()
+    pub const fn write_i8(&mut self, i: i8) {
+        self.0.write_i8(i);
     }
 
     #[inline]
-    fn write_usize(&mut self, i: usize) {
-        self.write_usize(i);
+    pub const fn write_i16(&mut self, i: i16) {
+        self.0.write_i16(i);
     }
 
     #[inline]
-    fn write_u8(&mut self, i: u8) {
-        self.write_u8(i);
+    pub const fn write_i32(&mut self, i: i32) {
+        self.0.write_i32(i);
     }
 
     #[inline]
-    fn write_u16(&mut self, i: u16) {
-        self.write_u16(i);
+    pub const fn write_i64(&mut self, i: i64) {
+        self.0.write_i64(i);
     }
 
     #[inline]
-    fn write_u32(&mut self, i: u32) {
-        self.write_u32(i);
+    pub const fn write_i128(&mut self, i: i128) {
+        self.0.write_i128(i);
     }
 
     #[inline]
-    fn write_u64(&mut self, i: u64) {
-        self.write_u64(i);
+    pub const fn write_isize(&mut self, i: isize) {
+        self.0.write_isize(i);
     }
 }
 
-impl SipHasher13 {
+impl SipHasher {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn write(&mut self, msg: &[u8]) {
-        self.hasher.write(msg)
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.0.write_u16_le(i);
     }
 
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn finish(&self) -> u64 {
-        self.hasher.finish()
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.0.write_u32_le(i);
     }
 
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn write_usize(&mut self, i: usize) {
-        self.hasher.write_usize(i);
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.0.write_u64_le(i);
     }
 
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn write_u8(&mut self, i: u8) {
-        self.hasher.write_u8(i);
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.0.write_u128_le(i);
     }
 
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn write_u16(&mut self, i: u16) {
-        self.hasher.write_u16(i);
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.0.write_i16_le(i);
     }
 
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn write_u32(&mut self, i: u32) {
-        self.hasher.write_u32(i);
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.0.write_i32_le(i);
     }
 
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn write_u64(&mut self, i: u64) {
-        self.hasher.write_u64(i);
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.0.write_i64_le(i);
     }
-}
 
-impl hash::Hasher for SipHasher24 {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    fn write(&mut self, msg: &[u8]) {
-        self.write(msg)
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.0.write_i128_le(i);
     }
 
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
     #[inline]
-    fn finish(&self) -> u64 {
-        self.finish()
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.0.write_usize_as_u64(i);
     }
 
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
     #[inline]
-    fn write_usize(&mut self, i: usize) {
-        self.write_usize(i);
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.0.write_isize_as_i64(i);
     }
 
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
     #[inline]
-    fn write_u8(&mut self, i: u8) {
-        self.write_u8(i);
+    pub const fn write_bool(&mut self, b: bool) {
+        self.0.write_bool(b);
     }
 
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
     #[inline]
-    fn write_u16(&mut self, i: u16) {
-        self.write_u16(i);
+    pub const fn write_char(&mut self, c: char) {
+        self.0.write_char(c);
     }
 
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
     #[inline]
-    fn write_u32(&mut self, i: u32) {
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.0.write_value(value);
+    }
+}
+
+impl hash::Hasher for SipHasher13 {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
         self.write_u32(i);
     }
 
@@ -625,9 +1568,44 @@ impl hash::Hasher for SipHasher24 {
     fn write_u64(&mut self, i: u64) {
         self.write_u64(i);
     }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
 }
 
-impl SipHasher24 {
+impl SipHasher13 {
     #[inline]
     pub const fn write(&mut self, msg: &[u8]) {
         self.hasher.write(msg)
@@ -662,9 +1640,149 @@ impl SipHasher24 {
     pub const fn write_u64(&mut self, i: u64) {
         self.hasher.write_u64(i);
     }
+
+    #[inline]
+    pub const fn write_u128(&mut self, i: u128) {
+        self.hasher.write_u128(i);
+    }
+
+    #[inline]
+    pub const fn write_i8(&mut self, i: i8) {
+        self.hasher.write_i8(i);
+    }
+
+    #[inline]
+    pub const fn write_i16(&mut self, i: i16) {
+        self.hasher.write_i16(i);
+    }
+
+    #[inline]
+    pub const fn write_i32(&mut self, i: i32) {
+        self.hasher.write_i32(i);
+    }
+
+    #[inline]
+    pub const fn write_i64(&mut self, i: i64) {
+        self.hasher.write_i64(i);
+    }
+
+    #[inline]
+    pub const fn write_i128(&mut self, i: i128) {
+        self.hasher.write_i128(i);
+    }
+
+    #[inline]
+    pub const fn write_isize(&mut self, i: isize) {
+        self.hasher.write_isize(i);
+    }
 }
 
-impl<S: Sip> hash::Hasher for Hasher<S> {
+impl SipHasher13 {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.hasher.write_u16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.hasher.write_u32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.hasher.write_u64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.hasher.write_u128_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.hasher.write_i16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.hasher.write_i32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.hasher.write_i64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.hasher.write_i128_le(i);
+    }
+
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
+    #[inline]
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.hasher.write_usize_as_u64(i);
+    }
+
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
+    #[inline]
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.hasher.write_isize_as_i64(i);
+    }
+
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
+    #[inline]
+    pub const fn write_bool(&mut self, b: bool) {
+        self.hasher.write_bool(b);
+    }
+
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
+    #[inline]
+    pub const fn write_char(&mut self, c: char) {
+        self.hasher.write_char(c);
+    }
+
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.hasher.write_value(value);
+    }
+}
+
+impl hash::Hasher for SipHasher24 {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
     #[inline]
     fn write_usize(&mut self, i: usize) {
         self.write_usize(i);
@@ -691,164 +1809,1385 @@ impl<S: Sip> hash::Hasher for Hasher<S> {
     }
 
     #[inline]
-    fn write(&mut self, msg: &[u8]) {
-        self.write(msg);
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
     }
 
     #[inline]
-    fn finish(&self) -> u64 {
-        self.finish()
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
     }
-}
 
-impl<S: Sip> Hasher<S> {
     #[inline]
-    const fn write_usize(&mut self, i: usize) {
-        self.short_write(&i, i.to_le() as u64);
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
     }
 
     #[inline]
-    const fn write_u8(&mut self, i: u8) {
-        self.short_write(&i, i as u64);
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
     }
 
     #[inline]
-    const fn write_u16(&mut self, i: u16) {
-        self.short_write(&i, i.to_le() as u64);
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
     }
 
     #[inline]
-    const fn write_u32(&mut self, i: u32) {
-        self.short_write(&i, i.to_le() as u64);
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
     }
 
     #[inline]
-    const fn write_u64(&mut self, i: u64) {
-        self.short_write(&i, i.to_le());
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
     }
+}
 
+impl SipHasher24 {
     #[inline]
-    const fn write(&mut self, msg: &[u8]) {
-        let length = msg.len();
-        self.length += length;
-
-        let mut needed = 0;
+    pub const fn write(&mut self, msg: &[u8]) {
+        self.hasher.write(msg)
+    }
 
-        if self.ntail != 0 {
-            needed = 8 - self.ntail;
-            if length < needed {
-                self.tail |= unsafe { u8to64_le(msg, 0, length) } << (8 * self.ntail);
-                self.ntail += length;
-                return;
-            } else {
-                self.tail |= unsafe { u8to64_le(msg, 0, needed) } << (8 * self.ntail);
-                self.state.v3 ^= self.tail;
-                self.state = c_rounds::<S>(self.state);
-                self.state.v0 ^= self.tail;
-                self.ntail = 0;
-            }
-        }
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
 
-        // Buffered tail is now flushed, process new input.
-        let len = length - needed;
-        let left = len & 0x7;
+    #[inline]
+    pub const fn write_usize(&mut self, i: usize) {
+        self.hasher.write_usize(i);
+    }
 
-        let mut i = needed;
-        while i < len - left {
-            let mi = unsafe { load_int_le!(msg, i, u64) };
+    #[inline]
+    pub const fn write_u8(&mut self, i: u8) {
+        self.hasher.write_u8(i);
+    }
 
-            self.state.v3 ^= mi;
-            self.state = c_rounds::<S>(self.state);
-            self.state.v0 ^= mi;
+    #[inline]
+    pub const fn write_u16(&mut self, i: u16) {
+        self.hasher.write_u16(i);
+    }
 
-            i += 8;
-        }
+    #[inline]
+    pub const fn write_u32(&mut self, i: u32) {
+        self.hasher.write_u32(i);
+    }
 
-        self.tail = unsafe { u8to64_le(msg, i, left) };
-        self.ntail = left;
+    #[inline]
+    pub const fn write_u64(&mut self, i: u64) {
+        self.hasher.write_u64(i);
     }
 
     #[inline]
-    const fn finish(&self) -> u64 {
-        self.finish128().h2
+    pub const fn write_u128(&mut self, i: u128) {
+        self.hasher.write_u128(i);
     }
-}
 
-impl<S: Sip> Clone for Hasher<S> {
     #[inline]
-    fn clone(&self) -> Hasher<S> {
-        Hasher {
-            k0: self.k0,
-            k1: self.k1,
-            length: self.length,
-            state: self.state,
-            tail: self.tail,
-            ntail: self.ntail,
-            _marker: self._marker,
-        }
+    pub const fn write_i8(&mut self, i: i8) {
+        self.hasher.write_i8(i);
     }
-}
 
-impl<S: Sip> Default for Hasher<S> {
-    /// Creates a `Hasher<S>` with the two initial keys set to 0.
     #[inline]
-    fn default() -> Hasher<S> {
-        Hasher::new_with_keys(0, 0)
+    pub const fn write_i16(&mut self, i: i16) {
+        self.hasher.write_i16(i);
     }
-}
 
-const fn c_rounds<S: Sip>(mut state: State) -> State {
-    let mut i = 0;
-    while i < S::C_ROUNDS {
-        compress!(state);
-        i += 1;
+    #[inline]
+    pub const fn write_i32(&mut self, i: i32) {
+        self.hasher.write_i32(i);
     }
-    state
-}
 
-const fn d_rounds<S: Sip>(mut state: State) -> State {
-    let mut i = 0;
-    while i < S::D_ROUNDS {
-        compress!(state);
-        i += 1;
+    #[inline]
+    pub const fn write_i64(&mut self, i: i64) {
+        self.hasher.write_i64(i);
     }
-    state
-}
 
-impl Hash128 {
-    /// Converts [`u128`] to [`Hash128`]
-    pub const fn from_u128(v: u128) -> Self {
-        Hash128 {
-            h1: v as u64,
-            h2: (v >> 64) as u64,
-        }
+    #[inline]
+    pub const fn write_i128(&mut self, i: i128) {
+        self.hasher.write_i128(i);
     }
 
-    /// Convert into a 16-bytes vector
-    pub const fn as_bytes(&self) -> [u8; 16] {
-        let mut bytes = [0u8; 16];
-        let h1 = self.h1.to_le();
-        let h2 = self.h2.to_le();
-        unsafe {
-            ptr::copy_nonoverlapping(&h1 as *const _ as *const u8, bytes.as_mut_ptr(), 8);
-            ptr::copy_nonoverlapping(&h2 as *const _ as *const u8, bytes.as_mut_ptr().add(8), 8);
-        }
-        bytes
+    #[inline]
+    pub const fn write_isize(&mut self, i: isize) {
+        self.hasher.write_isize(i);
     }
+}
 
-    /// Convert into a [`u128`]
+impl SipHasher24 {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
     #[inline]
-    pub const fn as_u128(&self) -> u128 {
-        let h1 = self.h1.to_le();
-        let h2 = self.h2.to_le();
-        h1 as u128 | ((h2 as u128) << 64)
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.hasher.write_u16_le(i);
     }
 
-    /// Convert into `(u64, u64)`
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.hasher.write_u32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.hasher.write_u64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.hasher.write_u128_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.hasher.write_i16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.hasher.write_i32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.hasher.write_i64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.hasher.write_i128_le(i);
+    }
+
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
+    #[inline]
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.hasher.write_usize_as_u64(i);
+    }
+
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
+    #[inline]
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.hasher.write_isize_as_i64(i);
+    }
+
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
+    #[inline]
+    pub const fn write_bool(&mut self, b: bool) {
+        self.hasher.write_bool(b);
+    }
+
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
+    #[inline]
+    pub const fn write_char(&mut self, c: char) {
+        self.hasher.write_char(c);
+    }
+
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.hasher.write_value(value);
+    }
+}
+
+impl<const C: usize, const D: usize> hash::Hasher for SipHasherCD<C, D> {
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write_u64(i);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
+}
+
+impl<const C: usize, const D: usize> SipHasherCD<C, D> {
+    #[inline]
+    pub const fn write(&mut self, msg: &[u8]) {
+        self.hasher.write(msg)
+    }
+
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    #[inline]
+    pub const fn write_usize(&mut self, i: usize) {
+        self.hasher.write_usize(i);
+    }
+
+    #[inline]
+    pub const fn write_u8(&mut self, i: u8) {
+        self.hasher.write_u8(i);
+    }
+
+    #[inline]
+    pub const fn write_u16(&mut self, i: u16) {
+        self.hasher.write_u16(i);
+    }
+
+    #[inline]
+    pub const fn write_u32(&mut self, i: u32) {
+        self.hasher.write_u32(i);
+    }
+
+    #[inline]
+    pub const fn write_u64(&mut self, i: u64) {
+        self.hasher.write_u64(i);
+    }
+
+    #[inline]
+    pub const fn write_u128(&mut self, i: u128) {
+        self.hasher.write_u128(i);
+    }
+
+    #[inline]
+    pub const fn write_i8(&mut self, i: i8) {
+        self.hasher.write_i8(i);
+    }
+
+    #[inline]
+    pub const fn write_i16(&mut self, i: i16) {
+        self.hasher.write_i16(i);
+    }
+
+    #[inline]
+    pub const fn write_i32(&mut self, i: i32) {
+        self.hasher.write_i32(i);
+    }
+
+    #[inline]
+    pub const fn write_i64(&mut self, i: i64) {
+        self.hasher.write_i64(i);
+    }
+
+    #[inline]
+    pub const fn write_i128(&mut self, i: i128) {
+        self.hasher.write_i128(i);
+    }
+
+    #[inline]
+    pub const fn write_isize(&mut self, i: isize) {
+        self.hasher.write_isize(i);
+    }
+}
+
+impl<const C: usize, const D: usize> SipHasherCD<C, D> {
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u16_le(&mut self, i: u16) {
+        self.hasher.write_u16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u32_le(&mut self, i: u32) {
+        self.hasher.write_u32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u64_le(&mut self, i: u64) {
+        self.hasher.write_u64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_u128_le(&mut self, i: u128) {
+        self.hasher.write_u128_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i16_le(&mut self, i: i16) {
+        self.hasher.write_i16_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i32_le(&mut self, i: i32) {
+        self.hasher.write_i32_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i64_le(&mut self, i: i64) {
+        self.hasher.write_i64_le(i);
+    }
+
+    /// Writes `i` in little-endian byte order. This is equivalent to
+    /// `write(&i.to_le_bytes())` and gives the same result on every platform.
+    #[inline]
+    pub const fn write_i128_le(&mut self, i: i128) {
+        self.hasher.write_i128_le(i);
+    }
+
+    /// Writes `i` as a little-endian `u64`. This is equivalent to
+    /// `write(&(i as u64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `usize`.
+    #[inline]
+    pub const fn write_usize_as_u64(&mut self, i: usize) {
+        self.hasher.write_usize_as_u64(i);
+    }
+
+    /// Writes `i` as a little-endian `i64`. This is equivalent to
+    /// `write(&(i as i64).to_le_bytes())` and gives the same result on every
+    /// platform, whatever the width of `isize`.
+    #[inline]
+    pub const fn write_isize_as_i64(&mut self, i: isize) {
+        self.hasher.write_isize_as_i64(i);
+    }
+
+    /// Writes `b` as a single byte, `0` or `1`. This is equivalent to
+    /// `write(&[b as u8])`.
+    #[inline]
+    pub const fn write_bool(&mut self, b: bool) {
+        self.hasher.write_bool(b);
+    }
+
+    /// Writes the scalar value of `c` as a little-endian `u32`. This is
+    /// equivalent to `write(&(c as u32).to_le_bytes())` and gives the same
+    /// result on every platform.
+    #[inline]
+    pub const fn write_char(&mut self, c: char) {
+        self.hasher.write_char(c);
+    }
+
+    /// Writes a structured value, with the same encoding as [`ConstHash`](crate::ConstHash).
+    #[inline]
+    pub const fn write_value(&mut self, value: &HashValue<'_>) {
+        self.hasher.write_value(value);
+    }
+}
+
+impl<S: Sip> hash::Hasher for Hasher<S> {
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write_u64(i);
+    }
+
+    #[inline]
+    fn write(&mut self, msg: &[u8]) {
+        self.write(msg);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish()
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u128(i);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_i8(i);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_i16(i);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_i32(i);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_i64(i);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_i128(i);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_isize(i);
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    #[inline]
+    const fn write_usize(&mut self, i: usize) {
+        self.short_write(&i, i.to_le() as u64);
+    }
+
+    #[inline]
+    const fn write_u8(&mut self, i: u8) {
+        self.short_write(&i, i as u64);
+    }
+
+    #[inline]
+    const fn write_u16(&mut self, i: u16) {
+        self.short_write(&i, i.to_le() as u64);
+    }
+
+    #[inline]
+    const fn write_u32(&mut self, i: u32) {
+        self.short_write(&i, i.to_le() as u64);
+    }
+
+    #[inline]
+    const fn write_u64(&mut self, i: u64) {
+        self.short_write(&i, i.to_le());
+    }
+
+    // Hashes the native-endian bytes of `i`, in two 64-bit steps.
+    #[inline]
+    const fn write_u128(&mut self, i: u128) {
+        let (lo, hi) = (i as u64, (i >> 64) as u64);
+        if cfg!(target_endian = "little") {
+            self.write_u64(lo);
+            self.write_u64(hi);
+        } else {
+            self.write_u64(hi);
+            self.write_u64(lo);
+        }
+    }
+
+    #[inline]
+    const fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8);
+    }
+
+    #[inline]
+    const fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    #[inline]
+    const fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    #[inline]
+    const fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    const fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    #[inline]
+    const fn write_isize(&mut self, i: isize) {
+        self.write_usize(i as usize);
+    }
+
+    #[inline]
+    const fn write(&mut self, msg: &[u8]) {
+        let length = msg.len();
+        self.length += length;
+
+        let mut needed = 0;
+
+        if self.ntail != 0 {
+            needed = 8 - self.ntail;
+            if length < needed {
+                self.tail |= unsafe { u8to64_le(msg, 0, length) } << (8 * self.ntail);
+                self.ntail += length;
+                return;
+            } else {
+                self.tail |= unsafe { u8to64_le(msg, 0, needed) } << (8 * self.ntail);
+                self.state.v3 ^= self.tail;
+                self.state = c_rounds::<S>(self.state);
+                self.state.v0 ^= self.tail;
+                self.ntail = 0;
+            }
+        }
+
+        // Buffered tail is now flushed, process new input.
+        let len = length - needed;
+        let left = len & 0x7;
+
+        let mut i = needed;
+        while i < len - left {
+            let mi = unsafe { load_int_le!(msg, i, u64) };
+
+            self.state.v3 ^= mi;
+            self.state = c_rounds::<S>(self.state);
+            self.state.v0 ^= mi;
+
+            i += 8;
+        }
+
+        self.tail = unsafe { u8to64_le(msg, i, left) };
+        self.ntail = left;
+    }
+
+    #[inline]
+    const fn finish(&self) -> u64 {
+        self.finish128().h2
+    }
+}
+
+impl<S: Sip> Hasher<S> {
+    // Unlike the methods above, the `_le` methods don't byte-swap on
+    // big-endian hardware: `short_write` takes the value whose little-endian
+    // bytes are to be hashed, which is the value itself.
+    #[inline]
+    const fn write_u16_le(&mut self, i: u16) {
+        self.short_write(&i, i as u64);
+    }
+
+    #[inline]
+    const fn write_u32_le(&mut self, i: u32) {
+        self.short_write(&i, i as u64);
+    }
+
+    #[inline]
+    const fn write_u64_le(&mut self, i: u64) {
+        self.short_write(&i, i);
+    }
+
+    #[inline]
+    const fn write_u128_le(&mut self, i: u128) {
+        self.write_u64_le(i as u64);
+        self.write_u64_le((i >> 64) as u64);
+    }
+
+    #[inline]
+    const fn write_i16_le(&mut self, i: i16) {
+        self.write_u16_le(i as u16);
+    }
+
+    #[inline]
+    const fn write_i32_le(&mut self, i: i32) {
+        self.write_u32_le(i as u32);
+    }
+
+    #[inline]
+    const fn write_i64_le(&mut self, i: i64) {
+        self.write_u64_le(i as u64);
+    }
+
+    #[inline]
+    const fn write_i128_le(&mut self, i: i128) {
+        self.write_u128_le(i as u128);
+    }
+
+    #[inline]
+    const fn write_usize_as_u64(&mut self, i: usize) {
+        self.write_u64_le(i as u64);
+    }
+
+    #[inline]
+    const fn write_isize_as_i64(&mut self, i: isize) {
+        self.write_i64_le(i as i64);
+    }
+
+    #[inline]
+    const fn write_bool(&mut self, b: bool) {
+        self.write_u8(b as u8);
+    }
+
+    #[inline]
+    const fn write_char(&mut self, c: char) {
+        self.write_u32_le(c as u32);
+    }
+
+    const fn write_value(&mut self, value: &HashValue<'_>) {
+        match *value {
+            HashValue::U8(i) => self.write_u8(i),
+            HashValue::U16(i) => self.write_u16_le(i),
+            HashValue::U32(i) => self.write_u32_le(i),
+            HashValue::U64(i) => self.write_u64_le(i),
+            HashValue::U128(i) => self.write_u128_le(i),
+            HashValue::Usize(i) => self.write_usize_as_u64(i),
+            HashValue::I8(i) => self.write_u8(i as u8),
+            HashValue::I16(i) => self.write_i16_le(i),
+            HashValue::I32(i) => self.write_i32_le(i),
+            HashValue::I64(i) => self.write_i64_le(i),
+            HashValue::I128(i) => self.write_i128_le(i),
+            HashValue::Isize(i) => self.write_isize_as_i64(i),
+            HashValue::Bool(b) => self.write_bool(b),
+            HashValue::Char(c) => self.write_char(c),
+            HashValue::Str(s) => {
+                self.write_usize_as_u64(s.len());
+                self.write(s.as_bytes());
+            }
+            HashValue::Bytes(bytes) => {
+                self.write_usize_as_u64(bytes.len());
+                self.write(bytes);
+            }
+            HashValue::Slice(items) => {
+                self.write_usize_as_u64(items.len());
+                self.write_values(items);
+            }
+            HashValue::Array(items) | HashValue::Tuple(items) => self.write_values(items),
+        }
+    }
+
+    const fn write_values(&mut self, items: &[HashValue<'_>]) {
+        let mut i = 0;
+        while i < items.len() {
+            self.write_value(&items[i]);
+            i += 1;
+        }
+    }
+}
+
+impl<S: Sip> Clone for Hasher<S> {
+    #[inline]
+    fn clone(&self) -> Hasher<S> {
+        Hasher {
+            key: self.key,
+            length: self.length,
+            state: self.state,
+            tail: self.tail,
+            ntail: self.ntail,
+            _marker: self._marker,
+        }
+    }
+}
+
+impl SipBuildHasher13 {
+    /// Creates a new `SipBuildHasher13` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> SipBuildHasher13 {
+        SipBuildHasher13::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipBuildHasher13` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> SipBuildHasher13 {
+        SipBuildHasher13 {
+            hasher: SipHasher13::new_with_keys(key0, key1),
+        }
+    }
+
+    /// Creates a `SipBuildHasher13` from a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipBuildHasher13 {
+        SipBuildHasher13 {
+            hasher: SipHasher13::new_with_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher13` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipBuildHasher13 {
+        SipBuildHasher13 {
+            hasher: SipHasher13::new_with_sip_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher13` keyed off a random key drawn from the
+    /// operating system.
+    #[cfg(feature = "std")]
+    pub fn random() -> SipBuildHasher13 {
+        SipBuildHasher13::new_with_sip_key(SipKey::random())
+    }
+
+    /// Get the keys used by this builder
+    pub const fn keys(&self) -> (u64, u64) {
+        self.hasher.keys()
+    }
+
+    /// Get the key used by this builder as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.sip_key()
+    }
+
+    /// Creates a new hasher keyed off this builder's keys.
+    #[inline]
+    pub const fn build_hasher(&self) -> SipHasher13 {
+        self.hasher
+    }
+}
+
+impl hash::BuildHasher for SipBuildHasher13 {
+    type Hasher = SipHasher13;
+
+    #[inline]
+    fn build_hasher(&self) -> SipHasher13 {
+        self.build_hasher()
+    }
+}
+
+impl SipBuildHasher24 {
+    /// Creates a new `SipBuildHasher24` with the two initial keys set to 0.
+    #[inline]
+    pub const fn new() -> SipBuildHasher24 {
+        SipBuildHasher24::new_with_keys(0, 0)
+    }
+
+    /// Creates a `SipBuildHasher24` that is keyed off the provided keys.
+    #[inline]
+    pub const fn new_with_keys(key0: u64, key1: u64) -> SipBuildHasher24 {
+        SipBuildHasher24 {
+            hasher: SipHasher24::new_with_keys(key0, key1),
+        }
+    }
+
+    /// Creates a `SipBuildHasher24` from a 16 byte key.
+    #[inline]
+    pub const fn new_with_key(key: &[u8; 16]) -> SipBuildHasher24 {
+        SipBuildHasher24 {
+            hasher: SipHasher24::new_with_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher24` keyed off the provided [`SipKey`].
+    #[inline]
+    pub const fn new_with_sip_key(key: SipKey) -> SipBuildHasher24 {
+        SipBuildHasher24 {
+            hasher: SipHasher24::new_with_sip_key(key),
+        }
+    }
+
+    /// Creates a `SipBuildHasher24` keyed off a random key drawn from the
+    /// operating system.
+    #[cfg(feature = "std")]
+    pub fn random() -> SipBuildHasher24 {
+        SipBuildHasher24::new_with_sip_key(SipKey::random())
+    }
+
+    /// Get the keys used by this builder
+    pub const fn keys(&self) -> (u64, u64) {
+        self.hasher.keys()
+    }
+
+    /// Get the key used by this builder as a [`SipKey`]
+    pub const fn sip_key(&self) -> SipKey {
+        self.hasher.sip_key()
+    }
+
+    /// Creates a new hasher keyed off this builder's keys.
+    #[inline]
+    pub const fn build_hasher(&self) -> SipHasher24 {
+        self.hasher
+    }
+}
+
+impl hash::BuildHasher for SipBuildHasher24 {
+    type Hasher = SipHasher24;
+
+    #[inline]
+    fn build_hasher(&self) -> SipHasher24 {
+        self.build_hasher()
+    }
+}
+
+impl<S: Sip> fmt::Debug for Hasher<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The state is derived from the key and the tail holds message
+        // bytes, so neither is printed.
+        f.debug_struct("Hasher")
+            .field("key", &self.key)
+            .field("length", &self.length)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Sip> Default for Hasher<S> {
+    /// Creates a `Hasher<S>` with the two initial keys set to 0.
+    #[inline]
+    fn default() -> Hasher<S> {
+        Hasher::new_with_keys(0, 0)
+    }
+}
+
+/// The serialized form of `Hasher<S>`, which keeps the `k0` and `k1` fields
+/// it had before the key moved into a `SipKey`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Hasher", bound = "")]
+struct RawHasher<S: Sip> {
+    k0: u64,
+    k1: u64,
+    length: usize,
+    state: State,
+    tail: u64,
+    ntail: usize,
+    _marker: PhantomData<S>,
+}
+
+#[cfg(feature = "serde")]
+impl<S: Sip> From<Hasher<S>> for RawHasher<S> {
+    fn from(hasher: Hasher<S>) -> RawHasher<S> {
+        let (k0, k1) = hasher.key.to_u64s();
+        RawHasher {
+            k0,
+            k1,
+            length: hasher.length,
+            state: hasher.state,
+            tail: hasher.tail,
+            ntail: hasher.ntail,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: Sip> From<RawHasher<S>> for Hasher<S> {
+    fn from(raw: RawHasher<S>) -> Hasher<S> {
+        Hasher {
+            key: SipKey::from_u64s(raw.k0, raw.k1),
+            length: raw.length,
+            state: raw.state,
+            tail: raw.tail,
+            ntail: raw.ntail,
+            _marker: PhantomData,
+        }
+    }
+}
+
+const fn c_rounds<S: Sip>(mut state: State) -> State {
+    let mut i = 0;
+    while i < S::C_ROUNDS {
+        compress!(state);
+        i += 1;
+    }
+    state
+}
+
+const fn d_rounds<S: Sip>(mut state: State) -> State {
+    let mut i = 0;
+    while i < S::D_ROUNDS {
+        compress!(state);
+        i += 1;
+    }
+    state
+}
+
+/// Processes the last block `b`, holding the message length and its trailing
+/// bytes, and returns the 128-bit hash.
+const fn finalize128<S: Sip>(mut state: State, b: u64) -> Hash128 {
+    state.v3 ^= b;
+    state = c_rounds::<S>(state);
+    state.v0 ^= b;
+
+    state.v2 ^= 0xee;
+    state = d_rounds::<S>(state);
+    let h1 = state.v0 ^ state.v1 ^ state.v2 ^ state.v3;
+
+    state.v1 ^= 0xdd;
+    state = d_rounds::<S>(state);
+    let h2 = state.v0 ^ state.v1 ^ state.v2 ^ state.v3;
+
+    Hash128 { h1, h2 }
+}
+
+impl Hash128 {
+    /// Converts [`u128`] to [`Hash128`]
+    ///
+    /// This is the inverse of [`as_u128`](Self::as_u128).
+    pub const fn from_u128(v: u128) -> Self {
+        Hash128 {
+            h1: v as u64,
+            h2: (v >> 64) as u64,
+        }
+    }
+
+    /// Creates a [`Hash128`] from its two 64-bit halves, `h1` being the
+    /// little-endian value of the first 8 bytes of [`as_bytes`](Self::as_bytes)
+    /// and `h2` that of the last 8 bytes.
+    #[inline]
+    pub const fn from_le_u64s(h1: u64, h2: u64) -> Self {
+        Hash128 { h1, h2 }
+    }
+
+    /// Converts a 16-bytes vector, as returned by [`as_bytes`](Self::as_bytes),
+    /// into a [`Hash128`]
+    #[inline]
+    pub const fn from_bytes(bytes: &[u8; 16]) -> Self {
+        let b = bytes;
+        Hash128 {
+            h1: u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+            h2: u64::from_le_bytes([b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]]),
+        }
+    }
+
+    /// Convert into a 16-bytes vector
+    pub const fn as_bytes(&self) -> [u8; 16] {
+        let b1 = self.h1.to_le_bytes();
+        let b2 = self.h2.to_le_bytes();
+        [
+            b1[0], b1[1], b1[2], b1[3], b1[4], b1[5], b1[6], b1[7], b2[0], b2[1], b2[2], b2[3],
+            b2[4], b2[5], b2[6], b2[7],
+        ]
+    }
+
+    /// Compares two hashes in constant time.
+    ///
+    /// Use this rather than comparing the fields when the hash is used as an
+    /// authentication tag.
+    #[inline]
+    pub const fn ct_eq(&self, other: &Hash128) -> bool {
+        crate::ct_eq(&self.as_bytes(), &other.as_bytes())
+    }
+
+    /// Convert into a [`u128`]
+    ///
+    /// The result is `h1 | (h2 << 64)`, which is the little-endian value of
+    /// [`as_bytes`](Self::as_bytes) on every platform. This is the same value
+    /// as `u128::from(hash)` and the inverse of [`from_u128`](Self::from_u128).
+    #[inline]
+    pub const fn as_u128(&self) -> u128 {
+        self.h1 as u128 | ((self.h2 as u128) << 64)
+    }
+
+    /// Convert into `(u64, u64)`
+    ///
+    /// The result is `(h1, h2)`, the little-endian values of the first and
+    /// last 8 bytes of [`as_bytes`](Self::as_bytes) on every platform. This is
+    /// the inverse of [`from_le_u64s`](Self::from_le_u64s).
     #[inline]
     pub const fn as_u64(&self) -> (u64, u64) {
-        let h1 = self.h1.to_le();
-        let h2 = self.h2.to_le();
-        (h1, h2)
+        (self.h1, self.h2)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for SipHasher {
+    type OutputSize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::crypto_common::KeySizeUser for SipHasher {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::KeyInit for SipHasher {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for SipHasher {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for SipHasher {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for SipHasher {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutputReset for SipHasher {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::MacMarker for SipHasher {}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for SipHasher13 {
+    type OutputSize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::crypto_common::KeySizeUser for SipHasher13 {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::KeyInit for SipHasher13 {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for SipHasher13 {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for SipHasher13 {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for SipHasher13 {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutputReset for SipHasher13 {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::MacMarker for SipHasher13 {}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for SipHasher24 {
+    type OutputSize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::crypto_common::KeySizeUser for SipHasher24 {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::KeyInit for SipHasher24 {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for SipHasher24 {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for SipHasher24 {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for SipHasher24 {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutputReset for SipHasher24 {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::MacMarker for SipHasher24 {}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::OutputSizeUser for SipHasherCD<C, D> {
+    type OutputSize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::crypto_common::KeySizeUser for SipHasherCD<C, D> {
+    type KeySize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::KeyInit for SipHasherCD<C, D> {
+    #[inline]
+    fn new(key: &digest::Key<Self>) -> Self {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(key);
+        Self::new_with_key(&bytes)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::Update for SipHasherCD<C, D> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::FixedOutput for SipHasherCD<C, D> {
+    #[inline]
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::Reset for SipHasherCD<C, D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::FixedOutputReset for SipHasherCD<C, D> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.finish128().as_bytes());
+        digest::Reset::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<const C: usize, const D: usize> digest::MacMarker for SipHasherCD<C, D> {}
+
+#[cfg(feature = "std")]
+impl std::io::Write for SipHasher {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for SipHasher13 {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for SipHasher24 {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const C: usize, const D: usize> std::io::Write for SipHasherCD<C, D> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<S: Sip> zeroize::Zeroize for Hasher<S> {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.length.zeroize();
+        self.state.v0.zeroize();
+        self.state.v2.zeroize();
+        self.state.v1.zeroize();
+        self.state.v3.zeroize();
+        self.tail.zeroize();
+        self.ntail.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipHasher {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipHasher13 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipHasher24 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const C: usize, const D: usize> zeroize::Zeroize for SipHasherCD<C, D> {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipBuildHasher13 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SipBuildHasher24 {
+    fn zeroize(&mut self) {
+        self.hasher.zeroize();
     }
 }
@@ -8,9 +8,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::hash::{Hash, Hasher};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, Hasher};
 
-use super::sip::{SipHasher, SipHasher13, SipHasher24};
+use super::sip::{
+    KeyedSip13, KeyedSip24, SipBuildHasher13, SipBuildHasher24, SipHasher, SipHasher13,
+    SipHasher24, SipHasherCD,
+};
+use super::{ConstHash, HashValue, SipKey};
 
 // Hash just the bytes of the slice, without length prefix
 struct Bytes<'a>(&'a [u8]);
@@ -241,6 +246,119 @@ fn test_siphash_2_4() {
         t += 1;
     }
 }
+
+#[test]
+#[allow(unused_must_use)]
+fn test_siphash_4_8() {
+    let vecs: [[u8; 8]; 64] = [
+        [0x41, 0xda, 0x38, 0x99, 0x2b, 0x05, 0x79, 0xc8],
+        [0x51, 0xb8, 0x95, 0x52, 0xf9, 0x14, 0x59, 0xc8],
+        [0x92, 0x37, 0x16, 0xf0, 0xbe, 0xdd, 0xc3, 0x33],
+        [0x6a, 0x46, 0xd4, 0x7d, 0x65, 0x47, 0xc1, 0x05],
+        [0xc2, 0x38, 0x59, 0x2b, 0x4a, 0xc1, 0xfa, 0x48],
+        [0xf6, 0xc2, 0xd7, 0xd9, 0xcf, 0x52, 0x47, 0xe1],
+        [0x6b, 0xb6, 0xbc, 0x34, 0xc8, 0x35, 0x55, 0x8e],
+        [0x47, 0xd7, 0x3f, 0x71, 0x5a, 0xbe, 0xfd, 0x4e],
+        [0x20, 0xb5, 0x8b, 0x9c, 0x07, 0x2f, 0xdb, 0x50],
+        [0x36, 0x31, 0x9a, 0xf3, 0x5e, 0xe1, 0x12, 0x53],
+        [0x48, 0xa9, 0xd0, 0xdb, 0x0a, 0x8d, 0x84, 0x8f],
+        [0xcc, 0x69, 0x39, 0x60, 0x36, 0x04, 0x0a, 0x81],
+        [0x4b, 0x6d, 0x68, 0x53, 0x7a, 0xa7, 0x97, 0x61],
+        [0x29, 0x37, 0x96, 0xe9, 0xf2, 0xc9, 0x50, 0x69],
+        [0x88, 0x43, 0x1b, 0xea, 0xa7, 0x62, 0x9a, 0x68],
+        [0xe0, 0xa6, 0xa9, 0x7d, 0xd5, 0x89, 0xd3, 0x83],
+        [0x55, 0x9c, 0xf5, 0x53, 0x80, 0xb2, 0xac, 0x70],
+        [0xd5, 0xb7, 0xc5, 0x11, 0x7a, 0xe3, 0x79, 0x4e],
+        [0x5a, 0x3c, 0x45, 0x46, 0x34, 0xad, 0x10, 0x2b],
+        [0xc0, 0xa4, 0x80, 0xaf, 0xa3, 0x5a, 0x3d, 0xbc],
+        [0x78, 0xc2, 0x27, 0x09, 0xe5, 0x28, 0x4b, 0xc8],
+        [0xef, 0x26, 0x70, 0x46, 0x0d, 0xeb, 0xd6, 0x9d],
+        [0xd9, 0x76, 0xef, 0x86, 0xa9, 0xd0, 0x84, 0xd8],
+        [0xe3, 0xd9, 0x81, 0x18, 0x19, 0xea, 0xd0, 0xe8],
+        [0x89, 0x33, 0x3c, 0xb5, 0x3e, 0xea, 0xec, 0x16],
+        [0x31, 0x15, 0x6c, 0x5f, 0x64, 0x73, 0x49, 0xc6],
+        [0xa5, 0x4c, 0xce, 0x35, 0x35, 0x76, 0x32, 0xa4],
+        [0x06, 0x5d, 0x89, 0x25, 0xc0, 0xa7, 0xd2, 0xfe],
+        [0x2b, 0xbb, 0xaa, 0x82, 0x22, 0x1a, 0x3a, 0x8b],
+        [0x87, 0x0b, 0xfb, 0xce, 0x64, 0x09, 0x7b, 0x70],
+        [0x40, 0xd8, 0xe0, 0xf9, 0x64, 0x95, 0xee, 0x8b],
+        [0x79, 0xfc, 0xa7, 0xf4, 0x0b, 0xfa, 0xdf, 0x12],
+        [0x00, 0x0b, 0xfb, 0xf2, 0x2f, 0x76, 0x9e, 0xd2],
+        [0x40, 0x68, 0x55, 0x91, 0xf8, 0xe5, 0x22, 0xfa],
+        [0x2b, 0xe6, 0xfe, 0x74, 0xd8, 0x14, 0x9d, 0x0d],
+        [0xba, 0x7e, 0x2f, 0x0e, 0x0b, 0x75, 0x60, 0xed],
+        [0x02, 0xe9, 0xe3, 0x84, 0xed, 0xa7, 0xe1, 0x97],
+        [0xc4, 0xe8, 0x0a, 0x62, 0x95, 0x27, 0x63, 0xb6],
+        [0x83, 0x27, 0xed, 0xc6, 0x5d, 0x5c, 0x6d, 0xd3],
+        [0x79, 0xfc, 0x64, 0xd1, 0x64, 0xa4, 0x2f, 0xc0],
+        [0x15, 0x4a, 0x75, 0x11, 0xcb, 0xfc, 0x61, 0x4e],
+        [0x8b, 0x14, 0x8d, 0x7c, 0xec, 0xa0, 0xe6, 0x6f],
+        [0xdf, 0xee, 0x69, 0xb6, 0x54, 0xc4, 0x03, 0xfa],
+        [0xc5, 0x8f, 0x36, 0xa6, 0x69, 0x7b, 0xb7, 0xc9],
+        [0xa6, 0xc5, 0xbe, 0x9c, 0x05, 0xc6, 0x31, 0x21],
+        [0xb5, 0x8a, 0x87, 0x59, 0xfb, 0xcd, 0x89, 0x31],
+        [0xd7, 0x68, 0x3a, 0x67, 0x04, 0xcc, 0xc4, 0x25],
+        [0xcb, 0x6a, 0xe6, 0xe1, 0xe5, 0xa2, 0x44, 0x8d],
+        [0x6e, 0x26, 0x69, 0x5b, 0x3a, 0x3a, 0x51, 0x73],
+        [0x78, 0x71, 0x07, 0xcf, 0x9f, 0x33, 0xac, 0x4a],
+        [0x16, 0x75, 0x90, 0xda, 0xd9, 0x7b, 0x74, 0x84],
+        [0x00, 0x6b, 0x68, 0x1e, 0xf0, 0x6b, 0xf3, 0x06],
+        [0x1c, 0x9b, 0x30, 0x02, 0x66, 0xef, 0xcf, 0xa6],
+        [0x28, 0x8d, 0x2f, 0x88, 0xd1, 0xb0, 0xb3, 0x4b],
+        [0xe0, 0x11, 0x06, 0xbd, 0xac, 0xf5, 0x6b, 0xfe],
+        [0xc0, 0x10, 0x1f, 0x0e, 0x5b, 0x6e, 0x03, 0x28],
+        [0xc3, 0xa7, 0x91, 0x45, 0x5b, 0x1b, 0x1c, 0x0a],
+        [0x57, 0x07, 0xaf, 0xe1, 0x9e, 0x0b, 0x3a, 0x0f],
+        [0xe6, 0x5a, 0x72, 0x29, 0xfe, 0x53, 0x59, 0x4f],
+        [0x00, 0x2f, 0x9d, 0xb9, 0xab, 0x1a, 0xaf, 0x4c],
+        [0x59, 0x28, 0xcb, 0x50, 0x44, 0xc1, 0x06, 0x06],
+        [0xd5, 0x38, 0x01, 0x96, 0x7b, 0x85, 0x73, 0x21],
+        [0x05, 0xdb, 0x36, 0x4f, 0x1a, 0x09, 0x99, 0xcc],
+        [0xe6, 0x77, 0x84, 0xbc, 0x55, 0x03, 0xde, 0x23],
+    ];
+
+    let k0 = 0x_07_06_05_04_03_02_01_00;
+    let k1 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+    let mut buf = Vec::new();
+    let mut t = 0;
+    let mut state_inc = SipHasherCD::<4, 8>::new_with_keys(k0, k1);
+
+    while t < 64 {
+        let vec = u8to64_le!(vecs[t], 0);
+        let out = hash_with(SipHasherCD::<4, 8>::new_with_keys(k0, k1), &Bytes(&buf));
+        assert_eq!(vec, out);
+
+        let full = hash_with(SipHasherCD::<4, 8>::new_with_keys(k0, k1), &Bytes(&buf));
+        let i = state_inc.finish();
+
+        assert_eq!(full, i);
+        assert_eq!(full, vec);
+
+        buf.push(t as u8);
+        Hasher::write(&mut state_inc, &[t as u8]);
+
+        t += 1;
+    }
+}
+
+#[test]
+fn test_siphash_cd_matches_fixed_rounds() {
+    let k0 = 0x_07_06_05_04_03_02_01_00;
+    let k1 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+    let mut buf = Vec::new();
+    for t in 0..64 {
+        assert_eq!(
+            SipHasherCD::<1, 3>::new_with_keys(k0, k1).hash(&buf),
+            SipHasher13::new_with_keys(k0, k1).hash(&buf)
+        );
+        assert_eq!(
+            SipHasherCD::<2, 4>::new_with_keys(k0, k1).hash(&buf),
+            SipHasher24::new_with_keys(k0, k1).hash(&buf)
+        );
+        buf.push(t as u8);
+    }
+}
+
 #[test]
 fn test_hash_idempotent() {
     let val64 = 0xdead_beef_dead_beef_u64;
@@ -321,6 +439,883 @@ fn test_hash_incremental() {
     _ = h;
 }
 
+#[test]
+fn test_const_key() {
+    const KEY: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    const K0: u64 = 0x_07_06_05_04_03_02_01_00;
+    const K1: u64 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+
+    const SIP_KEY: SipKey = SipKey::from_key_bytes(&KEY);
+    const SIP_KEY_BYTES: [u8; 16] = SIP_KEY.to_key_bytes();
+    assert_eq!(SIP_KEY.to_u64s(), (K0, K1));
+    assert_eq!(SIP_KEY_BYTES, KEY);
+    assert_eq!(SipKey::from_u64s(K0, K1).to_key_bytes(), KEY);
+
+    const H: SipHasher = SipHasher::new_with_key(&KEY);
+    const H13: SipHasher13 = SipHasher13::new_with_key(&KEY);
+    const H24: SipHasher24 = SipHasher24::new_with_key(&KEY);
+    const H48: SipHasherCD<4, 8> = SipHasherCD::new_with_key(&KEY);
+    const KEYS: [[u8; 16]; 4] = [H.key(), H13.key(), H24.key(), H48.key()];
+    assert_eq!(KEYS, [KEY; 4]);
+    assert_eq!(H.keys(), (K0, K1));
+    assert_eq!(H13.keys(), (K0, K1));
+    assert_eq!(H24.keys(), (K0, K1));
+    assert_eq!(H48.keys(), (K0, K1));
+
+    const B: SipBuildHasher13 = SipBuildHasher13::new_with_key(&KEY);
+    assert_eq!(B.keys(), (K0, K1));
+}
+
+#[test]
+fn test_sip_key() {
+    const K0: u64 = 0x_07_06_05_04_03_02_01_00;
+    const K1: u64 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+    const KEY: SipKey = SipKey::from_bytes([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+    assert_eq!(KEY.to_u64s(), (K0, K1));
+
+    const H13: SipHasher13 = SipHasher13::new_with_sip_key(KEY);
+    assert_eq!(H13.sip_key().to_u64s(), (K0, K1));
+    assert_eq!(
+        H13.hash(b"foo"),
+        SipHasher13::new_with_keys(K0, K1).hash(b"foo")
+    );
+    assert_eq!(SipHasher24::new_with_sip_key(KEY).keys(), (K0, K1));
+    assert_eq!(SipHasher::new_with_sip_key(KEY).keys(), (K0, K1));
+    assert_eq!(SipHasherCD::<4, 8>::new_with_sip_key(KEY).keys(), (K0, K1));
+    assert_eq!(SipBuildHasher24::new_with_sip_key(KEY).keys(), (K0, K1));
+}
+
+#[test]
+fn test_debug_redacts_key() {
+    const K0: u64 = 0x_1122_3344_5566_7788;
+    const K1: u64 = 0x_99aa_bbcc_ddee_ff00;
+    let key = SipKey::from_u64s(K0, K1);
+    assert_eq!(format!("{:?}", key), "SipKey { .. }");
+
+    let mut hasher = SipHasher13::new_with_sip_key(key);
+    hasher.write(b"secret message");
+    let outputs = [
+        format!("{:?}", hasher),
+        format!("{:#?}", hasher),
+        format!("{:?}", SipHasher24::new_with_keys(K0, K1)),
+        format!("{:?}", SipHasher::new_with_keys(K0, K1)),
+        format!("{:?}", SipBuildHasher13::new_with_keys(K0, K1)),
+    ];
+    for output in &outputs {
+        assert!(output.contains("SipKey { .. }"), "{}", output);
+        for secret in [K0, K1] {
+            assert!(!output.contains(&format!("{}", secret)), "{}", output);
+            assert!(!output.contains(&format!("{:x}", secret)), "{}", output);
+        }
+        assert!(!output.contains("v0"), "{}", output);
+        assert!(!output.contains("tail"), "{}", output);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_sip_key_random() {
+    let a = SipKey::random();
+    let b = SipKey::random();
+    assert_ne!(a.to_u64s(), b.to_u64s());
+}
+
+#[test]
+fn test_sip_key_from() {
+    let bytes = [1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(SipKey::from(bytes).to_u64s(), (1, 2));
+    assert_eq!(SipKey::from(&bytes).to_u64s(), (1, 2));
+    assert_eq!(SipKey::from((1, 2)).to_u64s(), (1, 2));
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn test_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut key = SipKey::from_u64s(1, 2);
+    key.zeroize();
+    assert_eq!(key.to_u64s(), (0, 0));
+
+    let mut hasher = SipHasher24::new_with_keys(1, 2);
+    hasher.write(b"foo");
+    hasher.zeroize();
+    assert_eq!(hasher.keys(), (0, 0));
+
+    let hasher = zeroize::Zeroizing::new(SipHasher13::new_with_keys(1, 2));
+    assert_eq!(
+        hasher.hash(b"foo"),
+        SipHasher13::new_with_keys(1, 2).hash(b"foo")
+    );
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn test_secret_sip_key() {
+    use super::SecretSipKey;
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    fn wiped_on_drop<T: ZeroizeOnDrop>(_: &T) {}
+
+    let key = SecretSipKey::from_u64s(1, 2);
+    wiped_on_drop(&key);
+    assert_eq!(key.expose().to_u64s(), (1, 2));
+    assert_eq!(format!("{:?}", key), "SecretSipKey { .. }");
+
+    let mut hasher = key.sip_hasher24();
+    wiped_on_drop(&hasher);
+    hasher.write(b"foo");
+    assert_eq!(
+        hasher.finish(),
+        SipHasher24::new_with_keys(1, 2).hash(b"foo")
+    );
+    assert_eq!(
+        key.sip128_hasher13().hash(b"foo"),
+        super::sip128::SipHasher13::new_with_keys(1, 2).hash(b"foo")
+    );
+
+    // Dropping runs the same wipe as `zeroize`.
+    let mut copy = key.clone();
+    copy.zeroize();
+    assert_eq!(copy.expose().to_u64s(), (0, 0));
+    let bytes = SecretSipKey::from_key_bytes(&[7; 16]);
+    assert_eq!(bytes.expose().to_key_bytes(), [7; 16]);
+}
+
+#[test]
+fn test_verify() {
+    let hasher = SipHasher24::new_with_keys(1, 2);
+    let tag = hasher.hash(b"message").to_le_bytes();
+    assert!(hasher.verify(b"message", &tag));
+    assert!(!hasher.verify(b"messagf", &tag));
+    assert!(!SipHasher24::new_with_keys(1, 3).verify(b"message", &tag));
+
+    for i in 0..8 {
+        let mut bad = tag;
+        bad[i] ^= 1;
+        assert!(!hasher.verify(b"message", &bad));
+    }
+
+    const TAG: [u8; 8] = SipHasher13::new().hash(b"").to_le_bytes();
+    const _: () = assert!(SipHasher13::new().verify(b"", &TAG));
+    assert!(SipHasher::new().verify(b"", &SipHasher::new().hash(b"").to_le_bytes()));
+    assert!(
+        SipHasherCD::<4, 8>::new().verify(b"", &SipHasherCD::<4, 8>::new().hash(b"").to_le_bytes())
+    );
+}
+
+#[test]
+fn test_ct_diff_no_early_exit() {
+    // If the comparison stopped at the first differing byte, the later
+    // differences would be missing from the accumulated result.
+    let a = [0u8; 16];
+    let mut b = [0u8; 16];
+    b[0] = 0x01;
+    b[7] = 0x10;
+    b[15] = 0x80;
+    assert_eq!(super::ct_diff(&a, &b), 0x91);
+    assert!(!super::ct_eq(&a, &b));
+
+    b[0] = 0;
+    assert_eq!(super::ct_diff(&a, &b), 0x90);
+    b[7] = 0;
+    assert_eq!(super::ct_diff(&a, &b), 0x80);
+    b[15] = 0;
+    assert_eq!(super::ct_diff(&a, &b), 0);
+    assert!(super::ct_eq(&a, &b));
+}
+
+macro_rules! check_portable_writes {
+    ($hasher:expr) => {{
+        for prefix in 0..8 {
+            let mut a = $hasher;
+            let mut b = $hasher;
+            a.write(&[0xaa; 8][..prefix]);
+            b.write(&[0xaa; 8][..prefix]);
+
+            a.write_u16_le(0x0102);
+            b.write(&0x0102u16.to_le_bytes());
+            a.write_u32_le(0x0304_0506);
+            b.write(&0x0304_0506u32.to_le_bytes());
+            a.write_u64_le(0x0708_090a_0b0c_0d0e);
+            b.write(&0x0708_090a_0b0c_0d0eu64.to_le_bytes());
+            a.write_u128_le(0x0f10_1112_1314_1516_1718_191a_1b1c_1d1e);
+            b.write(&0x0f10_1112_1314_1516_1718_191a_1b1c_1d1eu128.to_le_bytes());
+            a.write_i16_le(-2);
+            b.write(&(-2i16).to_le_bytes());
+            a.write_i32_le(-3);
+            b.write(&(-3i32).to_le_bytes());
+            a.write_i64_le(-4);
+            b.write(&(-4i64).to_le_bytes());
+            a.write_i128_le(-5);
+            b.write(&(-5i128).to_le_bytes());
+            a.write_usize_as_u64(6);
+            b.write(&6u64.to_le_bytes());
+            a.write_isize_as_i64(-7);
+            b.write(&(-7i64).to_le_bytes());
+            a.write_bool(true);
+            b.write(&[1]);
+            a.write_bool(false);
+            b.write(&[0]);
+            a.write_char('\u{1f980}');
+            b.write(&0x1f980u32.to_le_bytes());
+
+            assert_eq!(a.finish(), b.finish());
+        }
+    }};
+}
+
+#[test]
+fn test_portable_writes() {
+    check_portable_writes!(SipHasher::new_with_keys(1, 2));
+    check_portable_writes!(SipHasher13::new_with_keys(1, 2));
+    check_portable_writes!(SipHasher24::new_with_keys(1, 2));
+    check_portable_writes!(SipHasherCD::<4, 8>::new_with_keys(1, 2));
+
+    // Pinned, so that a platform producing different bytes fails.
+    const H: u64 = {
+        let mut hasher = SipHasher24::new_with_keys(1, 2);
+        hasher.write_u16_le(0x0102);
+        hasher.write_u128_le(0x0304_0506_0708_090a_0b0c_0d0e_0f10_1112);
+        hasher.write_usize_as_u64(3);
+        hasher.write_char('x');
+        hasher.write_bool(true);
+        hasher.finish()
+    };
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&[0x02, 0x01]);
+    bytes.extend_from_slice(&[
+        0x12, 0x11, 0x10, 0x0f, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04,
+        0x03,
+    ]);
+    bytes.extend_from_slice(&[3, 0, 0, 0, 0, 0, 0, 0]);
+    bytes.extend_from_slice(&[b'x', 0, 0, 0]);
+    bytes.push(1);
+    assert_eq!(H, SipHasher24::new_with_keys(1, 2).hash(&bytes));
+    assert_eq!(H, 0x92b6c216b8b63a70);
+}
+
+macro_rules! check_native_writes {
+    ($hasher:expr) => {{
+        for prefix in 0..8 {
+            let mut a = $hasher;
+            let mut b = $hasher;
+            let mut c = $hasher;
+            a.write(&[0xaa; 8][..prefix]);
+            b.write(&[0xaa; 8][..prefix]);
+            Hasher::write(&mut c, &[0xaa; 8][..prefix]);
+
+            let u = 0x0f10_1112_1314_1516_1718_191a_1b1c_1d1eu128;
+            a.write_u128(u);
+            b.write(&u.to_ne_bytes());
+            Hasher::write_u128(&mut c, u);
+            a.write_i8(-1);
+            b.write(&(-1i8).to_ne_bytes());
+            Hasher::write_i8(&mut c, -1);
+            a.write_i16(-2);
+            b.write(&(-2i16).to_ne_bytes());
+            Hasher::write_i16(&mut c, -2);
+            a.write_i32(-3);
+            b.write(&(-3i32).to_ne_bytes());
+            Hasher::write_i32(&mut c, -3);
+            a.write_i64(-4);
+            b.write(&(-4i64).to_ne_bytes());
+            Hasher::write_i64(&mut c, -4);
+            a.write_i128(-5);
+            b.write(&(-5i128).to_ne_bytes());
+            Hasher::write_i128(&mut c, -5);
+            a.write_isize(-6);
+            b.write(&(-6isize).to_ne_bytes());
+            Hasher::write_isize(&mut c, -6);
+
+            assert_eq!(a.finish(), b.finish());
+            assert_eq!(a.finish(), Hasher::finish(&c));
+        }
+    }};
+}
+
+#[test]
+fn test_native_writes() {
+    check_native_writes!(SipHasher::new_with_keys(1, 2));
+    check_native_writes!(SipHasher13::new_with_keys(1, 2));
+    check_native_writes!(SipHasher24::new_with_keys(1, 2));
+    check_native_writes!(SipHasherCD::<4, 8>::new_with_keys(1, 2));
+
+    const H: u64 = {
+        let mut hasher = SipHasher13::new();
+        hasher.write_i128(-1);
+        hasher.write_isize(-1);
+        hasher.finish()
+    };
+    let mut bytes = vec![0xff; 16];
+    bytes.extend_from_slice(&(-1isize).to_ne_bytes());
+    assert_eq!(H, SipHasher13::new().hash(&bytes));
+    assert_eq!(
+        hash(&u128::MAX),
+        SipHasher::new().hash(&u128::MAX.to_ne_bytes())
+    );
+}
+
+#[test]
+fn test_hash_value() {
+    const KEY: HashValue = HashValue::Tuple(&[HashValue::U32(7), HashValue::Str("foo")]);
+    const H: u64 = SipHasher13::new_with_keys(1, 2).hash_value(&KEY);
+
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    (7u32, "foo").const_hash(&mut hasher);
+    assert_eq!(hasher.finish(), H);
+
+    let mut bytes = vec![7, 0, 0, 0];
+    bytes.extend_from_slice(&3u64.to_le_bytes());
+    bytes.extend_from_slice(b"foo");
+    assert_eq!(SipHasher13::new_with_keys(1, 2).hash(&bytes), H);
+
+    // Length prefixes keep adjacent strings apart.
+    let split = |a: &str, b: &str| {
+        let mut hasher = SipHasher24::new();
+        (a, b).const_hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_ne!(split("ab", "c"), split("a", "bc"));
+}
+
+#[test]
+fn test_hash_value_matches_const_hash() {
+    const VALUES: &[HashValue] = &[
+        HashValue::U8(1),
+        HashValue::U16(0x0203),
+        HashValue::U32(0x0405_0607),
+        HashValue::U64(0x0809_0a0b_0c0d_0e0f),
+        HashValue::U128(u128::MAX / 3),
+        HashValue::Usize(17),
+        HashValue::I8(-1),
+        HashValue::I16(-2),
+        HashValue::I32(-3),
+        HashValue::I64(-4),
+        HashValue::I128(-5),
+        HashValue::Isize(-6),
+        HashValue::Bool(true),
+        HashValue::Char('\u{1f980}'),
+        HashValue::Str("crab"),
+        HashValue::Bytes(b"\x00\xff"),
+        HashValue::Slice(&[HashValue::U16(1), HashValue::U16(2)]),
+        HashValue::Array(&[HashValue::U8(3), HashValue::U8(4)]),
+    ];
+    const H: u64 = SipHasher24::new_with_keys(3, 4).hash_value(&HashValue::Tuple(VALUES));
+    const H_CD: u64 =
+        SipHasherCD::<4, 8>::new_with_keys(3, 4).hash_value(&HashValue::Slice(VALUES));
+
+    let mut hasher = SipHasher24::new_with_keys(3, 4);
+    (
+        (1u8, 0x0203u16, 0x0405_0607u32, 0x0809_0a0b_0c0d_0e0fu64),
+        (
+            u128::MAX / 3,
+            17usize,
+            -1i8,
+            -2i16,
+            -3i32,
+            -4i64,
+            -5i128,
+            -6isize,
+        ),
+        (
+            true,
+            '\u{1f980}',
+            "crab",
+            &b"\x00\xff"[..],
+            &[1u16, 2][..],
+            [3u8, 4],
+        ),
+    )
+        .const_hash(&mut hasher);
+    assert_eq!(hasher.finish(), H);
+
+    let mut hasher = SipHasher24::new_with_keys(3, 4);
+    HashValue::Tuple(VALUES).const_hash(&mut hasher);
+    assert_eq!(hasher.finish(), H);
+
+    let mut hasher = SipHasherCD::<4, 8>::new_with_keys(3, 4);
+    hasher.write_value(&HashValue::Slice(VALUES));
+    let mut expected = SipHasherCD::<4, 8>::new_with_keys(3, 4);
+    HashValue::Slice(VALUES).const_hash(&mut expected);
+    assert_eq!(hasher.finish(), H_CD);
+    assert_eq!(expected.finish(), H_CD);
+
+    let mut hasher = SipHasher::new_with_keys(3, 4);
+    VALUES[14].const_hash(&mut hasher);
+    assert_eq!(
+        hasher.finish(),
+        SipHasher::new_with_keys(3, 4).hash_value(&VALUES[14])
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_write() {
+    let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    std::io::copy(&mut &data[..], &mut hasher).unwrap();
+    assert_eq!(
+        hasher.finish(),
+        SipHasher13::new_with_keys(1, 2).hash(&data)
+    );
+
+    let mut hasher = SipHasherCD::<4, 8>::new();
+    std::io::Write::write_all(&mut hasher, &data[..10]).unwrap();
+    std::io::Write::write_all(&mut hasher, &data[10..]).unwrap();
+    std::io::Write::flush(&mut hasher).unwrap();
+    assert_eq!(hasher.finish(), SipHasherCD::<4, 8>::new().hash(&data));
+
+    let mut hasher = SipHasher::new();
+    std::io::Write::write_all(&mut hasher, &data).unwrap();
+    assert_eq!(hasher.finish(), SipHasher24::new().hash(&data));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_hashing_reader_writer() {
+    use crate::io::{HashingReader, HashingWriter};
+    use std::io::{self, Read, Write};
+
+    let data: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+    let expected = SipHasher24::new_with_keys(1, 2).hash(&data);
+
+    let mut reader = HashingReader::new(&data[..], SipHasher24::new_with_keys(1, 2));
+    let mut writer = HashingWriter::new(Vec::new(), SipHasher24::new_with_keys(1, 2));
+    io::copy(&mut reader, &mut writer).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(reader.hasher().finish(), expected);
+    let (copy, hasher) = writer.into_parts();
+    assert_eq!(copy, data);
+    assert_eq!(hasher.finish(), expected);
+
+    // Short reads and writes only hash what went through.
+    let mut reader = HashingReader::new(&data[..], SipHasher13::new());
+    let mut buf = [0; 100];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.get_ref().len(), 900);
+    assert_eq!(
+        reader.hasher().finish(),
+        SipHasher13::new().hash(&data[..100])
+    );
+
+    let mut out = [0u8; 10];
+    let mut writer = HashingWriter::new(&mut out[..], SipHasher13::new());
+    assert_eq!(writer.write(&data).unwrap(), 10);
+    assert_eq!(
+        writer.hasher().finish(),
+        SipHasher13::new().hash(&data[..10])
+    );
+}
+
+/// The SipHash-2-4 reference vectors, as in `test_siphash_2_4`.
+const SIPHASH_2_4_VECS: [[u8; 8]; 64] = [
+    [0x31, 0x0e, 0x0e, 0xdd, 0x47, 0xdb, 0x6f, 0x72],
+    [0xfd, 0x67, 0xdc, 0x93, 0xc5, 0x39, 0xf8, 0x74],
+    [0x5a, 0x4f, 0xa9, 0xd9, 0x09, 0x80, 0x6c, 0x0d],
+    [0x2d, 0x7e, 0xfb, 0xd7, 0x96, 0x66, 0x67, 0x85],
+    [0xb7, 0x87, 0x71, 0x27, 0xe0, 0x94, 0x27, 0xcf],
+    [0x8d, 0xa6, 0x99, 0xcd, 0x64, 0x55, 0x76, 0x18],
+    [0xce, 0xe3, 0xfe, 0x58, 0x6e, 0x46, 0xc9, 0xcb],
+    [0x37, 0xd1, 0x01, 0x8b, 0xf5, 0x00, 0x02, 0xab],
+    [0x62, 0x24, 0x93, 0x9a, 0x79, 0xf5, 0xf5, 0x93],
+    [0xb0, 0xe4, 0xa9, 0x0b, 0xdf, 0x82, 0x00, 0x9e],
+    [0xf3, 0xb9, 0xdd, 0x94, 0xc5, 0xbb, 0x5d, 0x7a],
+    [0xa7, 0xad, 0x6b, 0x22, 0x46, 0x2f, 0xb3, 0xf4],
+    [0xfb, 0xe5, 0x0e, 0x86, 0xbc, 0x8f, 0x1e, 0x75],
+    [0x90, 0x3d, 0x84, 0xc0, 0x27, 0x56, 0xea, 0x14],
+    [0xee, 0xf2, 0x7a, 0x8e, 0x90, 0xca, 0x23, 0xf7],
+    [0xe5, 0x45, 0xbe, 0x49, 0x61, 0xca, 0x29, 0xa1],
+    [0xdb, 0x9b, 0xc2, 0x57, 0x7f, 0xcc, 0x2a, 0x3f],
+    [0x94, 0x47, 0xbe, 0x2c, 0xf5, 0xe9, 0x9a, 0x69],
+    [0x9c, 0xd3, 0x8d, 0x96, 0xf0, 0xb3, 0xc1, 0x4b],
+    [0xbd, 0x61, 0x79, 0xa7, 0x1d, 0xc9, 0x6d, 0xbb],
+    [0x98, 0xee, 0xa2, 0x1a, 0xf2, 0x5c, 0xd6, 0xbe],
+    [0xc7, 0x67, 0x3b, 0x2e, 0xb0, 0xcb, 0xf2, 0xd0],
+    [0x88, 0x3e, 0xa3, 0xe3, 0x95, 0x67, 0x53, 0x93],
+    [0xc8, 0xce, 0x5c, 0xcd, 0x8c, 0x03, 0x0c, 0xa8],
+    [0x94, 0xaf, 0x49, 0xf6, 0xc6, 0x50, 0xad, 0xb8],
+    [0xea, 0xb8, 0x85, 0x8a, 0xde, 0x92, 0xe1, 0xbc],
+    [0xf3, 0x15, 0xbb, 0x5b, 0xb8, 0x35, 0xd8, 0x17],
+    [0xad, 0xcf, 0x6b, 0x07, 0x63, 0x61, 0x2e, 0x2f],
+    [0xa5, 0xc9, 0x1d, 0xa7, 0xac, 0xaa, 0x4d, 0xde],
+    [0x71, 0x65, 0x95, 0x87, 0x66, 0x50, 0xa2, 0xa6],
+    [0x28, 0xef, 0x49, 0x5c, 0x53, 0xa3, 0x87, 0xad],
+    [0x42, 0xc3, 0x41, 0xd8, 0xfa, 0x92, 0xd8, 0x32],
+    [0xce, 0x7c, 0xf2, 0x72, 0x2f, 0x51, 0x27, 0x71],
+    [0xe3, 0x78, 0x59, 0xf9, 0x46, 0x23, 0xf3, 0xa7],
+    [0x38, 0x12, 0x05, 0xbb, 0x1a, 0xb0, 0xe0, 0x12],
+    [0xae, 0x97, 0xa1, 0x0f, 0xd4, 0x34, 0xe0, 0x15],
+    [0xb4, 0xa3, 0x15, 0x08, 0xbe, 0xff, 0x4d, 0x31],
+    [0x81, 0x39, 0x62, 0x29, 0xf0, 0x90, 0x79, 0x02],
+    [0x4d, 0x0c, 0xf4, 0x9e, 0xe5, 0xd4, 0xdc, 0xca],
+    [0x5c, 0x73, 0x33, 0x6a, 0x76, 0xd8, 0xbf, 0x9a],
+    [0xd0, 0xa7, 0x04, 0x53, 0x6b, 0xa9, 0x3e, 0x0e],
+    [0x92, 0x59, 0x58, 0xfc, 0xd6, 0x42, 0x0c, 0xad],
+    [0xa9, 0x15, 0xc2, 0x9b, 0xc8, 0x06, 0x73, 0x18],
+    [0x95, 0x2b, 0x79, 0xf3, 0xbc, 0x0a, 0xa6, 0xd4],
+    [0xf2, 0x1d, 0xf2, 0xe4, 0x1d, 0x45, 0x35, 0xf9],
+    [0x87, 0x57, 0x75, 0x19, 0x04, 0x8f, 0x53, 0xa9],
+    [0x10, 0xa5, 0x6c, 0xf5, 0xdf, 0xcd, 0x9a, 0xdb],
+    [0xeb, 0x75, 0x09, 0x5c, 0xcd, 0x98, 0x6c, 0xd0],
+    [0x51, 0xa9, 0xcb, 0x9e, 0xcb, 0xa3, 0x12, 0xe6],
+    [0x96, 0xaf, 0xad, 0xfc, 0x2c, 0xe6, 0x66, 0xc7],
+    [0x72, 0xfe, 0x52, 0x97, 0x5a, 0x43, 0x64, 0xee],
+    [0x5a, 0x16, 0x45, 0xb2, 0x76, 0xd5, 0x92, 0xa1],
+    [0xb2, 0x74, 0xcb, 0x8e, 0xbf, 0x87, 0x87, 0x0a],
+    [0x6f, 0x9b, 0xb4, 0x20, 0x3d, 0xe7, 0xb3, 0x81],
+    [0xea, 0xec, 0xb2, 0xa3, 0x0b, 0x22, 0xa8, 0x7f],
+    [0x99, 0x24, 0xa4, 0x3c, 0xc1, 0x31, 0x57, 0x24],
+    [0xbd, 0x83, 0x8d, 0x3a, 0xaf, 0xbf, 0x8d, 0xb7],
+    [0x0b, 0x1a, 0x2a, 0x32, 0x65, 0xd5, 0x1a, 0xea],
+    [0x13, 0x50, 0x79, 0xa3, 0x23, 0x1c, 0xe6, 0x60],
+    [0x93, 0x2b, 0x28, 0x46, 0xe4, 0xd7, 0x06, 0x66],
+    [0xe1, 0x91, 0x5f, 0x5c, 0xb1, 0xec, 0xa4, 0x6c],
+    [0xf3, 0x25, 0x96, 0x5c, 0xa1, 0x6d, 0x62, 0x9f],
+    [0x57, 0x5f, 0xf2, 0x8e, 0x60, 0x38, 0x1b, 0xe5],
+    [0x72, 0x45, 0x06, 0xeb, 0x4c, 0x32, 0x8a, 0x95],
+];
+
+#[test]
+#[cfg(feature = "digest")]
+fn test_digest_mac() {
+    use digest::{FixedOutput, FixedOutputReset, KeyInit, Mac};
+
+    let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+    let mut buf = Vec::new();
+    for (t, vec) in SIPHASH_2_4_VECS.iter().enumerate() {
+        let mut mac = <SipHasher24 as Mac>::new_from_slice(&key).unwrap();
+        Mac::update(&mut mac, &buf);
+        assert_eq!(mac.finalize().into_bytes()[..], vec[..]);
+
+        let mut mac = <SipHasher as KeyInit>::new(&key.into());
+        Mac::update(&mut mac, &buf);
+        mac.verify_slice(vec).unwrap();
+
+        let mut mac = <SipHasherCD<2, 4> as KeyInit>::new(&key.into());
+        digest::Update::update(&mut mac, &buf);
+        assert_eq!(mac.finalize_fixed()[..], vec[..]);
+
+        buf.push(t as u8);
+    }
+
+    let mut mac = <SipHasher13 as KeyInit>::new(&key.into());
+    Mac::update(&mut mac, b"foo");
+    let first = mac.finalize_fixed_reset();
+    assert_eq!(
+        first[..],
+        SipHasher13::new_with_key(&key).hash(b"foo").to_le_bytes()
+    );
+    Mac::update(&mut mac, b"foo");
+    assert_eq!(mac.finalize_fixed(), first);
+
+    let mut mac = <SipHasher13 as KeyInit>::new(&key.into());
+    Mac::update(&mut mac, b"bar");
+    Mac::reset(&mut mac);
+    Mac::update(&mut mac, b"foo");
+    assert_eq!(mac.finalize_fixed(), first);
+    assert!(<SipHasher13 as Mac>::new_from_slice(&key[..15]).is_err());
+
+    // Nothing buffered before a reset leaks into the next output.
+    let empty = SipHasher13::new_with_key(&key).hash(b"").to_le_bytes();
+    let mut mac = <SipHasher13 as KeyInit>::new(&key.into());
+    Mac::update(&mut mac, b"bar");
+    Mac::reset(&mut mac);
+    assert_eq!(mac.finalize_fixed()[..], empty);
+    let mut mac = <SipHasher13 as KeyInit>::new(&key.into());
+    Mac::update(&mut mac, b"bar");
+    mac.finalize_fixed_reset();
+    assert_eq!(mac.finalize_fixed()[..], empty);
+}
+
+#[test]
+fn test_reset_and_rekey() {
+    const H: (u64, u64) = {
+        let mut hasher = SipHasher13::new_with_keys(1, 2);
+        hasher.write(b"foo");
+        let first = hasher.finish_and_reset();
+        hasher.write(b"foo");
+        (first, hasher.finish())
+    };
+    assert_eq!(H.0, SipHasher13::new_with_keys(1, 2).hash(b"foo"));
+    assert_eq!(H.0, H.1);
+
+    macro_rules! check {
+        ($hasher:expr) => {{
+            // The bytes buffered before a reset don't leak into the output.
+            let mut hasher = $hasher;
+            hasher.write(b"bar");
+            hasher.reset();
+            assert_eq!(hasher.finish(), $hasher.hash(b""));
+            hasher.write(b"bar");
+            hasher.reset();
+            hasher.write_u8(7);
+            assert_eq!(hasher.finish(), $hasher.hash(&[7]));
+
+            let mut hasher = $hasher;
+            hasher.write(&[1; 13]);
+            hasher.reset();
+            hasher.write(b"foo");
+            assert_eq!(hasher.finish(), $hasher.hash(b"foo"));
+
+            hasher.write(&[1; 13]);
+            hasher.rekey(3, 4);
+            assert_eq!(hasher.keys(), (3, 4));
+            hasher.write(b"bar");
+            assert_eq!(hasher.finish_and_reset(), {
+                let mut rekeyed = $hasher;
+                rekeyed.rekey(3, 4);
+                rekeyed.hash(b"bar")
+            });
+            assert_eq!(hasher.finish(), {
+                let mut rekeyed = $hasher;
+                rekeyed.rekey(3, 4);
+                rekeyed.finish()
+            });
+        }};
+    }
+    check!(SipHasher::new_with_keys(1, 2));
+    check!(SipHasher13::new_with_keys(1, 2));
+    check!(SipHasher24::new_with_keys(1, 2));
+    check!(SipHasherCD::<4, 8>::new_with_keys(1, 2));
+    assert_eq!(
+        {
+            let mut hasher = SipHasher24::new();
+            hasher.rekey(3, 4);
+            hasher.hash(b"bar")
+        },
+        SipHasher24::new_with_keys(3, 4).hash(b"bar")
+    );
+}
+
+#[test]
+fn test_keyed() {
+    const KEYED: KeyedSip13 = KeyedSip13::new_with_keys(1, 2);
+    const H: u64 = KEYED.hash(b"foo");
+    assert_eq!(H, SipHasher13::new_with_keys(1, 2).hash(b"foo"));
+
+    let k0 = 0x_07_06_05_04_03_02_01_00;
+    let k1 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+    let keyed = KeyedSip24::new_with_keys(k0, k1);
+    let mut buf = Vec::new();
+    for (t, vec) in SIPHASH_2_4_VECS.iter().enumerate() {
+        assert_eq!(keyed.hash(&buf).to_le_bytes(), *vec);
+        buf.push(t as u8);
+    }
+
+    let keyed13 = KeyedSip13::new_with_key(&[7; 16]);
+    for len in 0..40 {
+        let msg: Vec<u8> = (0..len).map(|i| i as u8 ^ 0x5a).collect();
+        assert_eq!(keyed13.hash(&msg), keyed13.hasher().hash(&msg));
+    }
+    for i in [0, 1, u64::MAX, 0x0123_4567_89ab_cdef] {
+        assert_eq!(keyed13.hash_u64(i), keyed13.hash(&i.to_le_bytes()));
+        assert_eq!(keyed.hash_u64(i), keyed.hash(&i.to_le_bytes()));
+    }
+
+    assert_eq!(keyed.keys(), (k0, k1));
+    assert_eq!(keyed.sip_key().to_u64s(), (k0, k1));
+    assert_eq!(keyed13.key(), [7; 16]);
+    assert_eq!(
+        KeyedSip24::default().hash(b""),
+        SipHasher24::new().hash(b"")
+    );
+    assert_eq!(
+        format!("{:?}", keyed),
+        "KeyedSip24 { keyed: Keyed { key: SipKey { .. }, .. } }"
+    );
+}
+
+macro_rules! check_arrays {
+    ($hasher:expr, $($n:literal)*) => {$(
+        let array: [u8; $n] = core::array::from_fn(|i| i as u8);
+        assert_eq!($hasher.hash_array(&array), $hasher.hash(&array));
+    )*};
+}
+
+macro_rules! check_fixed_size {
+    ($hasher:expr) => {{
+        // A fresh hasher, one with buffered bytes and one without.
+        for prefix in [0, 3, 8] {
+            let mut hasher = $hasher;
+            hasher.write(&[0xaa; 8][..prefix]);
+
+            for i in [0, 1, 0x0123_4567, u32::MAX] {
+                assert_eq!(hasher.hash_u32(i), hasher.hash(&i.to_le_bytes()));
+            }
+            for i in [0, 1, 0x0123_4567_89ab_cdef, u64::MAX] {
+                assert_eq!(hasher.hash_u64(i), hasher.hash(&i.to_le_bytes()));
+            }
+            for i in [0, 1, u128::MAX / 3, u128::MAX] {
+                assert_eq!(hasher.hash_u128(i), hasher.hash(&i.to_le_bytes()));
+            }
+
+            check_arrays!(hasher, 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 23 24 25 31 32 33 40);
+        }
+    }};
+}
+
+#[test]
+fn test_fixed_size_hashes() {
+    check_fixed_size!(SipHasher::new_with_keys(1, 2));
+    check_fixed_size!(SipHasher13::new_with_keys(1, 2));
+    check_fixed_size!(SipHasher24::new_with_keys(1, 2));
+    check_fixed_size!(SipHasherCD::<4, 8>::new_with_keys(1, 2));
+
+    const H: u64 = SipHasher13::new_with_keys(1, 2).hash_u64(42);
+    assert_eq!(
+        H,
+        SipHasher13::new_with_keys(1, 2).hash(&42u64.to_le_bytes())
+    );
+    const A: u64 = SipHasher24::new().hash_array(b"hello, world");
+    assert_eq!(A, SipHasher24::new().hash(b"hello, world"));
+}
+
+macro_rules! check_hash_many {
+    ($hasher:expr) => {{
+        let data: Vec<u8> = (0..100).map(|i| (i * 13) as u8).collect();
+        let lengths = [0, 7, 8, 9, 70, 1, 15, 16, 17, 3, 3, 3, 100, 0, 64, 5, 33];
+        for prefix in [0, 3, 8] {
+            let mut hasher = $hasher;
+            hasher.write(&[0xaa; 8][..prefix]);
+
+            for count in 0..=lengths.len() {
+                let inputs: Vec<&[u8]> = lengths[..count].iter().map(|&n| &data[..n]).collect();
+                let expected: Vec<u64> = inputs.iter().map(|input| hasher.hash(input)).collect();
+
+                let mut out = vec![0; count];
+                hasher.hash_many(&inputs, &mut out);
+                assert_eq!(out, expected);
+
+                let mut out = vec![0; count];
+                hasher.hash_many_scalar(&inputs, &mut out);
+                assert_eq!(out, expected);
+            }
+        }
+    }};
+}
+
+#[test]
+fn test_hash_many() {
+    check_hash_many!(SipHasher::new_with_keys(1, 2));
+    check_hash_many!(SipHasher13::new_with_keys(1, 2));
+    check_hash_many!(SipHasher24::new_with_keys(1, 2));
+    check_hash_many!(SipHasherCD::<4, 8>::new_with_keys(1, 2));
+
+    const INPUTS: [&[u8]; 5] = [b"", b"a", b"hello, world", b"12345678", b"foo"];
+    const H: [u64; 5] = {
+        let mut out = [0; 5];
+        SipHasher13::new_with_keys(1, 2).hash_many_scalar(&INPUTS, &mut out);
+        out
+    };
+    for (input, h) in INPUTS.iter().zip(H) {
+        assert_eq!(SipHasher13::new_with_keys(1, 2).hash(input), h);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_hash_many_length_mismatch() {
+    SipHasher13::new().hash_many(&[b"foo"], &mut [0; 2]);
+}
+
+#[test]
+fn test_hash_many_backends() {
+    use crate::many::Backend;
+    use crate::{Sip13Rounds, Sip24Rounds};
+
+    let mut backends = vec![Backend::Scalar, Backend::detect()];
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("sse2") {
+            backends.push(Backend::Sse2);
+        }
+        if is_x86_feature_detected!("avx2") {
+            backends.push(Backend::Avx2);
+        }
+    }
+
+    let data: Vec<u8> = (0..40).map(|i| i as u8).collect();
+    let init = [1, 2, 3, 4];
+    for backend in backends {
+        for lengths in [[0, 1, 8, 40], [7, 7, 7, 7], [39, 0, 16, 9]] {
+            let inputs: Vec<&[u8]> = lengths.iter().map(|&n| &data[..n]).collect();
+            for count in 0..=backend.lanes() {
+                let group = &inputs[..count];
+                let expected = crate::many::hash_group::<Sip13Rounds, true>(init, 5, group);
+                let out = backend.hash_group::<Sip13Rounds, true>(init, 5, group);
+                assert_eq!(out[..count], expected[..count], "{:?}", backend);
+
+                let expected = crate::many::hash_group::<Sip24Rounds, false>(init, 0, group);
+                let out = backend.hash_group::<Sip24Rounds, false>(init, 0, group);
+                for l in 0..count {
+                    assert_eq!(out[l][0], expected[l][0], "{:?}", backend);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_build_hasher() {
+    const BUILDER: SipBuildHasher13 = SipBuildHasher13::new_with_keys(1, 2);
+    let val64 = 0xdead_beef_dead_beef_u64;
+    assert_eq!(BUILDER.keys(), (1, 2));
+    assert_eq!(
+        BUILDER.hash_one(val64),
+        hash_with(SipHasher13::new_with_keys(1, 2), &val64)
+    );
+
+    let builder = SipBuildHasher24::new_with_keys(1, 2);
+    assert_eq!(
+        builder.hash_one(val64),
+        hash_with(SipHasher24::new_with_keys(1, 2), &val64)
+    );
+
+    let mut map = HashMap::with_hasher(BUILDER);
+    map.insert("foo", 1);
+    map.insert("bar", 2);
+    assert_eq!(map.get("foo"), Some(&1));
+    assert_eq!(map.get("bar"), Some(&2));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_build_hasher_random() {
+    let a = SipBuildHasher13::random();
+    let b = SipBuildHasher13::random();
+    assert_ne!(a.keys(), b.keys());
+    assert_ne!(a.keys(), (0, 0));
+}
+
+#[test]
+fn test_hash_macros() {
+    const KEY: (u64, u64) = (0x_07_06_05_04_03_02_01_00, 0x_0f_0e_0d_0c_0b_0a_09_08);
+    const KEY_BYTES: &[u8; 16] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    const FOO13: u64 = crate::siphash13!(KEY, "foo");
+    const FOO24: u64 = crate::siphash24!(KEY_BYTES, b"foo");
+    const R: &[u8] = b"r";
+    const FOOBAR: u64 = crate::siphash13!(KEY, "foo", b"ba", R);
+    const EMPTY: u64 = crate::siphash24!(KEY);
+    const FILE: u64 = crate::siphash13!(KEY, include_bytes!("../COPYING"));
+
+    let hasher13 = SipHasher13::new_with_keys(KEY.0, KEY.1);
+    let hasher24 = SipHasher24::new_with_keys(KEY.0, KEY.1);
+    assert_eq!(FOO13, hasher13.hash(b"foo"));
+    assert_eq!(FOO24, hasher24.hash(b"foo"));
+    assert_eq!(FOOBAR, hasher13.hash(b"foobar"));
+    assert_eq!(EMPTY, hasher24.hash(b""));
+    assert_eq!(FILE, hasher13.hash(include_bytes!("../COPYING")));
+
+    let name = "foobar";
+    match hasher13.hash(name.as_bytes()) {
+        FOO13 => panic!("matched the wrong constant"),
+        FOOBAR => {}
+        _ => panic!("no constant matched"),
+    }
+}
+
 #[test]
 #[cfg(all(feature = "serde", feature = "serde_json"))]
 fn test_hash_serde() {
@@ -330,3 +1325,36 @@ fn test_hash_serde() {
     let deserialized: u64 = serde_json::from_str(&serialized).unwrap();
     assert_eq!(hash, deserialized);
 }
+
+#[test]
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+fn test_hasher_serde() {
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    hasher.write(b"foo");
+    let serialized = serde_json::to_string(&hasher).unwrap();
+    // The key is still serialized as the `k0` and `k1` fields.
+    assert!(serialized.starts_with("{\"hasher\":{\"k0\":1,\"k1\":2,\"length\":3,"));
+    let deserialized: SipHasher13 = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.finish(), hasher.finish());
+}
+
+#[test]
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+fn test_keyed_serde() {
+    // Only the key is serialized, and the state is derived from it again.
+    let keyed = KeyedSip24::new_with_keys(1, 2);
+    let serialized = serde_json::to_string(&keyed).unwrap();
+    assert_eq!(serialized, "{\"keyed\":{\"k0\":1,\"k1\":2}}");
+    let deserialized: KeyedSip24 = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.hash(b"foo"), keyed.hash(b"foo"));
+
+    let deserialized: KeyedSip13 = serde_json::from_str("{\"keyed\":{\"k0\":3,\"k1\":4}}").unwrap();
+    assert_eq!(
+        deserialized.hash(b"foo"),
+        deserialized.hasher().hash(b"foo")
+    );
+    assert_eq!(
+        deserialized.hash(b"foo"),
+        SipHasher13::new_with_keys(3, 4).hash(b"foo")
+    );
+}
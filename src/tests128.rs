@@ -8,9 +8,15 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::hash::{Hash, Hasher};
 
-use super::sip128::{Hasher128, SipHasher, SipHasher13, SipHasher24};
+use super::sip128::{
+    Hash128, Hasher128, SipBuildHasher13, SipBuildHasher24, SipHasher, SipHasher13, SipHasher24,
+    SipHasherCD,
+};
+use super::{ConstHash, HashValue};
 
 // Hash just the bytes of the slice, without length prefix
 struct Bytes<'a>(&'a [u8]);
@@ -102,6 +108,123 @@ fn test_siphash128_2_4() {
     }
 }
 
+#[test]
+#[allow(unused_must_use)]
+fn test_siphash128_4_8() {
+    #[rustfmt::skip]
+    let vecs: [[u8; 16]; 64] = [
+        [0x1f, 0x64, 0xce, 0x58, 0x6d, 0xa9, 0x04, 0xe9, 0xcf, 0xec, 0xe8, 0x54, 0x83, 0xa7, 0x0a, 0x6c],
+        [0x47, 0x34, 0x5d, 0xa8, 0xef, 0x4c, 0x79, 0x47, 0x6a, 0xf2, 0x7c, 0xa7, 0x91, 0xc7, 0xa2, 0x80],
+        [0xe1, 0x49, 0x5f, 0xa3, 0x96, 0xca, 0x2d, 0xc6, 0x22, 0x73, 0x81, 0x5f, 0x18, 0x82, 0x21, 0xa4],
+        [0xc7, 0xa2, 0x73, 0x84, 0x4a, 0xc5, 0x4e, 0x83, 0x5a, 0x9c, 0xb6, 0x7f, 0x81, 0x05, 0x76, 0x02],
+        [0x54, 0x1f, 0x52, 0xbb, 0xf4, 0x3e, 0xce, 0x4e, 0x2a, 0x95, 0xc8, 0xe0, 0x1f, 0x65, 0x6d, 0xef],
+        [0x17, 0x97, 0x3b, 0xd4, 0x0d, 0xf3, 0x48, 0x15, 0x24, 0x4f, 0x99, 0x0c, 0xbf, 0x12, 0xbe, 0x5d],
+        [0x6b, 0x0b, 0x36, 0x0d, 0x56, 0x32, 0x80, 0xcd, 0xb1, 0x7d, 0x56, 0xc9, 0x08, 0xe1, 0xf5, 0xff],
+        [0xed, 0x00, 0xe1, 0x3b, 0x18, 0x4b, 0xf1, 0xc2, 0x72, 0x6b, 0x8b, 0x54, 0xff, 0xd2, 0xee, 0xe0],
+        [0xa7, 0xd9, 0x46, 0x13, 0x8f, 0xf9, 0xed, 0xf5, 0x36, 0x4a, 0x5a, 0x23, 0xaf, 0xca, 0xe0, 0x63],
+        [0x9e, 0x73, 0x14, 0xb7, 0x54, 0x5c, 0xec, 0xa3, 0x8b, 0x9a, 0x55, 0x49, 0xe4, 0xfb, 0x0b, 0xe8],
+        [0x58, 0x6c, 0x62, 0xc6, 0x84, 0x89, 0xd1, 0x68, 0xae, 0xe6, 0x5b, 0x88, 0x9a, 0xb9, 0x12, 0x75],
+        [0xe6, 0x71, 0x52, 0xa6, 0x4c, 0xa3, 0xd1, 0x47, 0xc4, 0xab, 0x84, 0x1e, 0x2f, 0x2e, 0x7a, 0x99],
+        [0x7f, 0x1c, 0x7a, 0xea, 0x90, 0x8d, 0xe5, 0x2e, 0x3e, 0x9e, 0x08, 0x83, 0xee, 0xa8, 0x16, 0xaf],
+        [0xde, 0x82, 0x7a, 0xbf, 0x92, 0xb7, 0x33, 0x92, 0x3f, 0x35, 0x33, 0x0d, 0xb5, 0xef, 0x4a, 0x34],
+        [0x59, 0x75, 0x63, 0x64, 0x0f, 0x37, 0x9a, 0xc5, 0x37, 0x67, 0x8e, 0xe2, 0x35, 0x4c, 0x7d, 0xf9],
+        [0x28, 0x4d, 0x03, 0x30, 0x3a, 0x45, 0x3a, 0x59, 0x3d, 0x78, 0xf7, 0xfa, 0xdc, 0x90, 0x62, 0xcb],
+        [0x91, 0x4a, 0xc7, 0xa2, 0x59, 0x7f, 0x63, 0xb7, 0xc0, 0xfd, 0xe5, 0xab, 0x8d, 0x4e, 0xad, 0x9c],
+        [0x0d, 0x51, 0x15, 0xa4, 0x4b, 0xa4, 0x55, 0xee, 0x3a, 0x45, 0x3b, 0x95, 0xce, 0x87, 0xc3, 0xcb],
+        [0x54, 0x9b, 0x93, 0x9d, 0x0b, 0xf1, 0xd8, 0x94, 0x83, 0x37, 0x88, 0x5a, 0x84, 0xce, 0x79, 0x14],
+        [0x6c, 0x17, 0x97, 0x69, 0xcd, 0x34, 0x8a, 0xeb, 0xd2, 0xfb, 0x13, 0x57, 0x8c, 0x72, 0xb4, 0x6c],
+        [0xaa, 0xd0, 0x36, 0xc1, 0x38, 0xc9, 0x57, 0xe0, 0x68, 0x2a, 0x00, 0xee, 0x2f, 0x86, 0x40, 0x8b],
+        [0x21, 0xb1, 0xee, 0xc4, 0x2f, 0xb6, 0x70, 0xbf, 0xee, 0x90, 0x44, 0xff, 0x4e, 0xd7, 0x3a, 0x26],
+        [0x05, 0x93, 0xa1, 0xd6, 0x29, 0x97, 0xed, 0x37, 0x46, 0x53, 0xc9, 0x17, 0x46, 0x3f, 0x14, 0xeb],
+        [0x11, 0x3d, 0x31, 0x62, 0x77, 0x19, 0xf9, 0x1e, 0xa0, 0xf1, 0xff, 0xc6, 0x86, 0x57, 0xe2, 0x4e],
+        [0xb3, 0x39, 0x4c, 0xf7, 0x2d, 0xe0, 0x6a, 0xdd, 0x0e, 0x73, 0x14, 0xf0, 0xc2, 0x52, 0xc4, 0xd6],
+        [0x92, 0x2a, 0x98, 0xda, 0x9d, 0x35, 0xc3, 0x41, 0xe2, 0x45, 0x6b, 0xe4, 0xcd, 0x63, 0x89, 0xd2],
+        [0x59, 0x6b, 0x62, 0x30, 0xf7, 0x57, 0xb3, 0x4a, 0xa2, 0xdc, 0xea, 0x50, 0xcb, 0xb2, 0x8d, 0x4d],
+        [0xc2, 0x4e, 0xe4, 0x97, 0xd5, 0x5b, 0x7e, 0x80, 0x06, 0x84, 0xdf, 0x75, 0x65, 0x59, 0xee, 0x48],
+        [0x5e, 0x9c, 0xb6, 0xa1, 0x36, 0x68, 0x1e, 0xd4, 0x5e, 0x2b, 0x9d, 0xe4, 0xdc, 0x01, 0x81, 0x77],
+        [0xbf, 0xfa, 0x39, 0xca, 0x86, 0x56, 0xd3, 0x04, 0x79, 0x33, 0xed, 0xfe, 0x9d, 0x81, 0x78, 0xb2],
+        [0x18, 0x22, 0x94, 0x18, 0xa1, 0xd0, 0x79, 0x5a, 0x35, 0x7a, 0x80, 0x3a, 0x81, 0x34, 0xae, 0xa3],
+        [0x4a, 0x3e, 0x96, 0xff, 0x53, 0x47, 0x4e, 0x2e, 0x73, 0x7b, 0x69, 0x57, 0x1a, 0x77, 0xb0, 0x6e],
+        [0xfe, 0xd5, 0xf0, 0xf9, 0xd0, 0x37, 0x72, 0x84, 0x2e, 0x2f, 0x57, 0x2f, 0x63, 0xf1, 0x94, 0x50],
+        [0x39, 0x33, 0x58, 0x86, 0xc1, 0xf9, 0x42, 0x63, 0xc4, 0x0c, 0x66, 0x29, 0xc6, 0xbc, 0x44, 0x6f],
+        [0xee, 0xa5, 0xf9, 0x3b, 0xb3, 0x87, 0x10, 0xb0, 0x8b, 0x2c, 0x46, 0x97, 0x19, 0x8b, 0xbf, 0x9f],
+        [0x80, 0x6e, 0xc7, 0xb6, 0x70, 0x4f, 0x72, 0x0e, 0x37, 0x43, 0x12, 0x06, 0x61, 0x66, 0xd4, 0x3a],
+        [0x6e, 0x69, 0xed, 0x9d, 0xf0, 0xc9, 0x39, 0xb4, 0x9d, 0xaf, 0xee, 0xae, 0x60, 0x47, 0xb2, 0xa2],
+        [0x93, 0xc7, 0x7b, 0xf2, 0x98, 0xb6, 0xf9, 0xc7, 0x94, 0xa2, 0x30, 0x17, 0x7f, 0x2f, 0xd7, 0x38],
+        [0xff, 0xad, 0x9c, 0xd9, 0x8c, 0x2a, 0xa8, 0x75, 0xda, 0xff, 0x3a, 0x2a, 0x4c, 0xe6, 0x0c, 0xe6],
+        [0x4d, 0x99, 0x2f, 0xfd, 0xf9, 0x4a, 0x93, 0xcd, 0xcd, 0x64, 0xef, 0x76, 0x57, 0xf5, 0x10, 0xe3],
+        [0x32, 0x70, 0x62, 0x4e, 0x24, 0xe0, 0xa1, 0x1e, 0xa1, 0x86, 0xe0, 0x96, 0xbe, 0x1b, 0xce, 0x9b],
+        [0x31, 0xe8, 0xbb, 0xe0, 0xcb, 0x4e, 0xff, 0x51, 0x1f, 0xff, 0xc7, 0xc4, 0x09, 0x34, 0x31, 0x77],
+        [0xcb, 0xe1, 0x7d, 0x05, 0x87, 0x9a, 0xd9, 0x07, 0x64, 0x8a, 0x12, 0xa0, 0x70, 0x16, 0xab, 0x5b],
+        [0x88, 0x48, 0xd4, 0x43, 0x70, 0xe9, 0x8b, 0xe2, 0xd5, 0xd2, 0x8b, 0x46, 0x36, 0x6a, 0x0a, 0xfc],
+        [0xb7, 0xff, 0xd1, 0xb2, 0x42, 0x10, 0x76, 0xa9, 0x0c, 0xb5, 0xcf, 0x65, 0x54, 0x09, 0x5e, 0x0c],
+        [0x6a, 0x6b, 0x66, 0x6c, 0xd5, 0x23, 0xa8, 0xf6, 0xbb, 0xd8, 0x84, 0xfe, 0x1f, 0xd1, 0x05, 0x0c],
+        [0xa8, 0xfe, 0x8a, 0x83, 0x50, 0xfb, 0xf5, 0xc8, 0x05, 0xf1, 0x8c, 0xbd, 0x30, 0x13, 0x62, 0x24],
+        [0xcc, 0xe7, 0x11, 0x7a, 0xee, 0x82, 0x36, 0xf2, 0xeb, 0x3a, 0x96, 0x94, 0xd5, 0x7e, 0x62, 0xb5],
+        [0x3a, 0x25, 0xf0, 0xe4, 0xfc, 0x28, 0xb7, 0x0c, 0x6b, 0x30, 0x90, 0xba, 0xfe, 0xf6, 0x9f, 0x04],
+        [0x3f, 0x05, 0xe6, 0x26, 0x74, 0x9f, 0xc4, 0x8b, 0x81, 0x06, 0xf8, 0xe4, 0x44, 0x31, 0xdd, 0x4a],
+        [0x76, 0x68, 0x79, 0xf9, 0x76, 0x72, 0x16, 0x5c, 0x0a, 0xff, 0xd5, 0xfa, 0xdc, 0x77, 0x34, 0x5b],
+        [0x43, 0x71, 0xa0, 0x5a, 0xb6, 0x6c, 0x59, 0x8b, 0xc9, 0xc2, 0x84, 0x94, 0xa1, 0xdd, 0x2f, 0x0e],
+        [0x65, 0xf8, 0x5b, 0xd3, 0xa2, 0xa5, 0xf1, 0xba, 0x1f, 0x22, 0xb6, 0xef, 0xd6, 0xe0, 0x02, 0x66],
+        [0x76, 0xcf, 0x61, 0xda, 0xe5, 0x4b, 0x22, 0xef, 0xca, 0x6a, 0x9f, 0x22, 0x8a, 0xaf, 0x66, 0x11],
+        [0x6c, 0xdc, 0xc2, 0xe3, 0x9f, 0xdb, 0xa2, 0x9f, 0x88, 0x53, 0x90, 0xab, 0x9d, 0xa4, 0x84, 0xda],
+        [0xe1, 0xee, 0xac, 0xea, 0xcc, 0x3b, 0x67, 0xb2, 0xd8, 0xe4, 0xe2, 0x61, 0x7b, 0x2f, 0xaa, 0x5a],
+        [0x0b, 0xd2, 0x9f, 0x6f, 0x4c, 0xe1, 0x0f, 0x17, 0x78, 0xd6, 0xb0, 0x2e, 0xd5, 0xab, 0x5a, 0x6d],
+        [0xad, 0x18, 0x9f, 0x15, 0x6a, 0x52, 0x26, 0x7c, 0xe0, 0x87, 0x45, 0x83, 0x5b, 0x65, 0xa6, 0x07],
+        [0x0f, 0x6b, 0x99, 0x71, 0x72, 0x25, 0x66, 0xd4, 0x3d, 0xec, 0x6b, 0x99, 0xe3, 0x1c, 0x21, 0x8f],
+        [0xa1, 0xa4, 0xc8, 0xfa, 0x4f, 0x3d, 0xf4, 0x66, 0xd3, 0xf3, 0x9c, 0x6f, 0x3d, 0x9e, 0x1a, 0x74],
+        [0x3b, 0x1a, 0x3d, 0xb8, 0x8c, 0xf0, 0xc2, 0x1f, 0xc1, 0xa6, 0xd8, 0xa7, 0x2d, 0x9e, 0xf9, 0x1d],
+        [0xd1, 0x48, 0x68, 0x02, 0xef, 0xc0, 0x00, 0x28, 0x56, 0xc3, 0x63, 0x5a, 0x8a, 0x69, 0x2e, 0xe5],
+        [0xee, 0xa1, 0x5f, 0x8f, 0x7c, 0xae, 0x19, 0x99, 0xfd, 0x56, 0x49, 0x31, 0xc2, 0x2c, 0x1c, 0x3c],
+        [0x63, 0xf5, 0xae, 0x63, 0x28, 0xc4, 0xdb, 0x93, 0x20, 0x79, 0x61, 0xee, 0x90, 0x6b, 0xd4, 0xa5],
+    ];
+
+    let k0 = 0x_07_06_05_04_03_02_01_00;
+    let k1 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+    let mut buf = Vec::new();
+    let mut t = 0;
+    let mut state_inc = SipHasherCD::<4, 8>::new_with_keys(k0, k1);
+
+    while t < 64 {
+        let vec = vecs[t];
+        let out = hash_with(SipHasherCD::<4, 8>::new_with_keys(k0, k1), &Bytes(&buf));
+        assert_eq!(vec, out[..]);
+
+        let full = hash_with(SipHasherCD::<4, 8>::new_with_keys(k0, k1), &Bytes(&buf));
+        let i = state_inc.finish128().as_bytes();
+
+        assert_eq!(full, i);
+        assert_eq!(full, vec);
+
+        buf.push(t as u8);
+        Hasher::write(&mut state_inc, &[t as u8]);
+
+        t += 1;
+    }
+}
+
+#[test]
+fn test_siphash128_cd_matches_fixed_rounds() {
+    let k0 = 0x_07_06_05_04_03_02_01_00;
+    let k1 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+    let mut buf = Vec::new();
+    for t in 0..64 {
+        assert_eq!(
+            SipHasherCD::<1, 3>::new_with_keys(k0, k1)
+                .hash(&buf)
+                .as_bytes(),
+            SipHasher13::new_with_keys(k0, k1).hash(&buf).as_bytes()
+        );
+        assert_eq!(
+            SipHasherCD::<2, 4>::new_with_keys(k0, k1)
+                .hash(&buf)
+                .as_bytes(),
+            SipHasher24::new_with_keys(k0, k1).hash(&buf).as_bytes()
+        );
+        buf.push(t as u8);
+    }
+}
+
 #[test]
 fn test_siphash128_simple() {
     let array: &[u8] = &[1, 2, 3];
@@ -123,6 +246,539 @@ fn test_siphash128_incremental() {
     _ = h;
 }
 
+#[test]
+fn test_siphash128_const_key() {
+    const KEY: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    const H: SipHasher = SipHasher::new_with_key(&KEY);
+    const H13: SipHasher13 = SipHasher13::new_with_key(&KEY);
+    const H24: SipHasher24 = SipHasher24::new_with_key(&KEY);
+    const H48: SipHasherCD<4, 8> = SipHasherCD::new_with_key(&KEY);
+    const KEYS: [[u8; 16]; 4] = [H.key(), H13.key(), H24.key(), H48.key()];
+    assert_eq!(KEYS, [KEY; 4]);
+    assert_eq!(
+        H24.hash(b"").as_bytes(),
+        [163, 129, 127, 4, 186, 37, 168, 230, 109, 246, 114, 20, 199, 85, 2, 147]
+    );
+
+    const B: SipBuildHasher24 = SipBuildHasher24::new_with_key(&KEY);
+    assert_eq!(B.keys(), H24.keys());
+}
+
+#[test]
+fn test_siphash128_debug_redacts_key() {
+    const K0: u64 = 0x_1122_3344_5566_7788;
+    const K1: u64 = 0x_99aa_bbcc_ddee_ff00;
+    let outputs = [
+        format!("{:?}", SipHasher13::new_with_keys(K0, K1)),
+        format!("{:?}", SipHasher24::new_with_keys(K0, K1)),
+        format!("{:?}", SipBuildHasher24::new_with_keys(K0, K1)),
+    ];
+    for output in &outputs {
+        for secret in [K0, K1] {
+            assert!(!output.contains(&format!("{}", secret)), "{}", output);
+            assert!(!output.contains(&format!("{:x}", secret)), "{}", output);
+        }
+        assert!(!output.contains("v0"), "{}", output);
+    }
+}
+
+#[test]
+fn test_siphash128_verify() {
+    let hasher = SipHasher24::new_with_keys(1, 2);
+    let tag = hasher.hash(b"packet");
+    assert!(hasher.verify(b"packet", &tag.as_bytes()));
+    assert!(!hasher.verify(b"packeu", &tag.as_bytes()));
+
+    for i in 0..16 {
+        let mut bad = tag.as_bytes();
+        bad[i] ^= 0x80;
+        assert!(!hasher.verify(b"packet", &bad));
+    }
+
+    let other = SipHasher24::new_with_keys(1, 2).hash(b"packet");
+    assert!(tag.ct_eq(&other));
+    assert!(!tag.ct_eq(&hasher.hash(b"other")));
+    let mut h1_only = other;
+    h1_only.h2 ^= 1;
+    assert!(!tag.ct_eq(&h1_only));
+}
+
+#[test]
+fn test_hash128_traits() {
+    let k0 = 0x_07_06_05_04_03_02_01_00;
+    let k1 = 0x_0f_0e_0d_0c_0b_0a_09_08;
+    let hasher = SipHasher24::new_with_keys(k0, k1);
+    let empty = hasher.hash(b"");
+
+    assert_eq!(format!("{}", empty), "a3817f04ba25a8e66df67214c7550293");
+    assert_eq!(format!("{:x}", empty), "a3817f04ba25a8e66df67214c7550293");
+    assert_eq!(format!("{:X}", empty), "A3817F04BA25A8E66DF67214C7550293");
+    assert_eq!(
+        format!("{:#x}", empty),
+        "0xa3817f04ba25a8e66df67214c7550293"
+    );
+
+    assert_eq!("a3817f04ba25a8e66df67214c7550293".parse(), Ok(empty));
+    assert_eq!("A3817F04BA25A8E66DF67214C7550293".parse(), Ok(empty));
+    assert!("a3817f04ba25a8e66df67214c75502".parse::<Hash128>().is_err());
+    assert!("a3817f04ba25a8e66df67214c7550293ff"
+        .parse::<Hash128>()
+        .is_err());
+    assert!("g3817f04ba25a8e66df67214c7550293"
+        .parse::<Hash128>()
+        .is_err());
+
+    // The formatting flags are honored, and the prefixed forms parse back.
+    assert_eq!(
+        format!("{:>36x}", empty),
+        "    a3817f04ba25a8e66df67214c7550293"
+    );
+    assert_eq!(
+        format!("{:*<34X}", empty),
+        "A3817F04BA25A8E66DF67214C7550293**"
+    );
+    assert_eq!(
+        format!("{:#036x}", empty),
+        "0x00a3817f04ba25a8e66df67214c7550293"
+    );
+    for s in [
+        format!("{:#x}", empty),
+        format!("{:#X}", empty),
+        format!("{:#}", empty),
+    ] {
+        assert_eq!(s.parse(), Ok(empty));
+    }
+    assert!("0x".parse::<Hash128>().is_err());
+    assert!("0xa3817f04ba25a8e66df67214c75502"
+        .parse::<Hash128>()
+        .is_err());
+
+    let mut hashes = Vec::new();
+    let mut set = HashSet::new();
+    for i in 0..16u8 {
+        let h = hasher.hash(&[i]);
+        assert_eq!(h.to_string().parse(), Ok(h));
+        assert!(set.insert(h));
+        assert!(!set.insert(h));
+        hashes.push(h);
+    }
+    assert!(set.contains(&hasher.hash(&[3])));
+    assert_eq!(hasher.hash(&[3]), hashes[3]);
+    assert_ne!(hashes[3], hashes[4]);
+
+    // Sorting hashes gives the order of their `as_u128` values, `h2` being
+    // the high half.
+    hashes.sort();
+    let mut values: Vec<u128> = hashes.iter().map(Hash128::as_u128).collect();
+    values.sort();
+    assert_eq!(
+        hashes.iter().map(Hash128::as_u128).collect::<Vec<_>>(),
+        values
+    );
+    for w in hashes.windows(2) {
+        assert!(w[0] < w[1]);
+    }
+    assert!(Hash128 { h1: 1, h2: 0 } < Hash128 { h1: 0, h2: 1 });
+}
+
+#[test]
+fn test_hash128_from_bytes() {
+    const BYTES: [u8; 16] = [
+        163, 129, 127, 4, 186, 37, 168, 230, 109, 246, 114, 20, 199, 85, 2, 147,
+    ];
+    const H: Hash128 = Hash128::from_bytes(&BYTES);
+    const ROUND_TRIP: [u8; 16] = H.as_bytes();
+    assert_eq!(ROUND_TRIP, BYTES);
+    assert_eq!(
+        H,
+        SipHasher24::new_with_key(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15])
+            .hash(b"")
+    );
+    assert_eq!(H.h1, 0xe6a825ba047f81a3);
+    assert_eq!(H.h2, 0x930255c71472f66d);
+    assert_eq!(Hash128::from_le_u64s(H.h1, H.h2), H);
+
+    assert_eq!(Hash128::try_from(&BYTES[..]), Ok(H));
+    let err = Hash128::try_from(&BYTES[..15]).unwrap_err();
+    assert_eq!(err.slice_len(), 15);
+    assert_eq!(
+        err.to_string(),
+        "a 128-bit hash must be 16 bytes long, got 15"
+    );
+    assert!(Hash128::try_from(&[0u8; 17][..]).is_err());
+
+    for i in 0..64u8 {
+        let h = SipHasher13::new_with_keys(1, 2).hash(&[i]);
+        assert_eq!(Hash128::from_bytes(&h.as_bytes()), h);
+        assert_eq!(Hash128::try_from(&h.as_bytes()[..]), Ok(h));
+    }
+}
+
+#[test]
+fn test_hash128_numeric_accessors() {
+    let key = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    let h = SipHasher24::new_with_key(&key).hash(b"");
+    let bytes = [
+        163, 129, 127, 4, 186, 37, 168, 230, 109, 246, 114, 20, 199, 85, 2, 147,
+    ];
+
+    assert_eq!(h.as_bytes(), bytes);
+    assert_eq!(h.as_u64(), (0xe6a825ba047f81a3, 0x930255c71472f66d));
+    assert_eq!(h.as_u128(), 0x930255c71472f66d_e6a825ba047f81a3);
+    assert_eq!(h.as_u128(), u128::from_le_bytes(bytes));
+    assert_eq!(u128::from(h), h.as_u128());
+    assert_eq!(Hash128::from_u128(h.as_u128()), h);
+    assert_eq!(Hash128::from(h.as_u128()), h);
+    let (h1, h2) = h.as_u64();
+    assert_eq!(Hash128::from_le_u64s(h1, h2), h);
+
+    let h = SipHasher13::new_with_key(&key).hash(b"");
+    assert_eq!(h.as_u64(), (0xbea58827b2bc7ee7, 0x013030dd6adb62fd));
+    assert_eq!(h.as_u128(), 0x013030dd6adb62fd_bea58827b2bc7ee7);
+    assert_eq!(u128::from(h), h.as_u128());
+}
+
+#[test]
+fn test_siphash128_portable_writes() {
+    for prefix in 0..8 {
+        let mut a = SipHasher13::new_with_keys(1, 2);
+        let mut b = SipHasher13::new_with_keys(1, 2);
+        a.write(&[0x55; 8][..prefix]);
+        b.write(&[0x55; 8][..prefix]);
+
+        a.write_u128_le(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10);
+        b.write(&0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128.to_le_bytes());
+        a.write_i64_le(-1);
+        b.write(&(-1i64).to_le_bytes());
+        a.write_u16_le(0xabcd);
+        b.write(&0xabcdu16.to_le_bytes());
+        a.write_usize_as_u64(usize::MAX);
+        b.write(&(usize::MAX as u64).to_le_bytes());
+        a.write_isize_as_i64(-1);
+        b.write(&(-1i64).to_le_bytes());
+        a.write_char('é');
+        b.write(&0xe9u32.to_le_bytes());
+        a.write_bool(true);
+        b.write(&[1]);
+
+        assert_eq!(a.finish128(), b.finish128());
+    }
+
+    let mut a = SipHasher24::new();
+    a.write_u32_le(7);
+    a.write_i32_le(-7);
+    a.write_i16_le(-7);
+    a.write_u64_le(7);
+    a.write_i128_le(-7);
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&7u32.to_le_bytes());
+    bytes.extend_from_slice(&(-7i32).to_le_bytes());
+    bytes.extend_from_slice(&(-7i16).to_le_bytes());
+    bytes.extend_from_slice(&7u64.to_le_bytes());
+    bytes.extend_from_slice(&(-7i128).to_le_bytes());
+    assert_eq!(a.finish128(), SipHasher24::new().hash(&bytes));
+    assert_eq!(
+        SipHasher::new().hash(&bytes),
+        SipHasher24::new().hash(&bytes)
+    );
+    let mut c = SipHasherCD::<4, 8>::new();
+    c.write_bool(false);
+    assert_eq!(c.finish128(), SipHasherCD::<4, 8>::new().hash(&[0]));
+}
+
+#[test]
+fn test_siphash128_native_writes() {
+    for prefix in 0..8 {
+        let mut a = SipHasher13::new_with_keys(1, 2);
+        let mut b = SipHasher13::new_with_keys(1, 2);
+        a.write(&[0x55; 8][..prefix]);
+        b.write(&[0x55; 8][..prefix]);
+
+        Hasher::write_u128(&mut a, 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10);
+        b.write(&0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128.to_ne_bytes());
+        Hasher::write_i8(&mut a, -1);
+        b.write(&[0xff]);
+        Hasher::write_i16(&mut a, -2);
+        b.write(&(-2i16).to_ne_bytes());
+        Hasher::write_i32(&mut a, -3);
+        b.write(&(-3i32).to_ne_bytes());
+        a.write_i64(-4);
+        b.write(&(-4i64).to_ne_bytes());
+        a.write_i128(-5);
+        b.write(&(-5i128).to_ne_bytes());
+        a.write_isize(-6);
+        b.write(&(-6isize).to_ne_bytes());
+
+        assert_eq!(a.finish128(), b.finish128());
+    }
+
+    let mut a = SipHasher24::new();
+    let mut b = SipHasherCD::<2, 4>::new();
+    let mut c = SipHasher::new();
+    a.write_u128(u128::MAX - 1);
+    b.write_u128(u128::MAX - 1);
+    c.write_u128(u128::MAX - 1);
+    let expected = SipHasher24::new().hash(&(u128::MAX - 1).to_ne_bytes());
+    assert_eq!(a.finish128(), expected);
+    assert_eq!(b.finish128(), expected);
+    assert_eq!(c.finish128(), expected);
+}
+
+#[test]
+fn test_siphash128_hash_value() {
+    const KEY: HashValue = HashValue::Tuple(&[
+        HashValue::U32(7),
+        HashValue::Str("foo"),
+        HashValue::Slice(&[HashValue::Char('x'), HashValue::Char('y')]),
+        HashValue::Bool(false),
+    ]);
+    const H: Hash128 = SipHasher13::new_with_keys(1, 2).hash_value(&KEY);
+
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    (7u32, "foo", &['x', 'y'][..], false).const_hash(&mut hasher);
+    assert_eq!(hasher.finish128(), H);
+
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    (7u32, "foo", ['x', 'y'], false).const_hash(&mut hasher);
+    assert_ne!(hasher.finish128(), H);
+
+    let mut runtime = SipHasher24::new();
+    KEY.const_hash(&mut runtime);
+    let mut incremental = SipHasherCD::<2, 4>::new();
+    incremental.write_value(&KEY);
+    let expected = SipHasher24::new().hash_value(&KEY);
+    assert_eq!(runtime.finish128(), expected);
+    assert_eq!(incremental.finish128(), expected);
+    assert_eq!(SipHasher::new().hash_value(&KEY), expected);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_siphash128_io_write() {
+    let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    std::io::copy(&mut &data[..], &mut hasher).unwrap();
+    assert_eq!(
+        hasher.finish128(),
+        SipHasher13::new_with_keys(1, 2).hash(&data)
+    );
+
+    let mut hasher = crate::io::HashingWriter::new(std::io::sink(), SipHasher::new());
+    std::io::copy(&mut &data[..], &mut hasher).unwrap();
+    assert_eq!(hasher.hasher().finish128(), SipHasher24::new().hash(&data));
+    assert_eq!(
+        hasher.hasher().finish128(),
+        SipHasherCD::<2, 4>::new().hash(&data)
+    );
+}
+
+#[test]
+#[cfg(feature = "digest")]
+fn test_siphash128_digest_mac() {
+    use digest::{FixedOutput, FixedOutputReset, KeyInit, Mac};
+
+    let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+    let vec: [u8; 16] = [
+        163, 129, 127, 4, 186, 37, 168, 230, 109, 246, 114, 20, 199, 85, 2, 147,
+    ];
+
+    let mac = <SipHasher24 as Mac>::new_from_slice(&key).unwrap();
+    assert_eq!(mac.finalize().into_bytes()[..], vec);
+    let mac = <SipHasher as KeyInit>::new(&key.into());
+    mac.verify_slice(&vec).unwrap();
+    let mac = <SipHasherCD<2, 4> as KeyInit>::new(&key.into());
+    assert_eq!(mac.finalize_fixed()[..], vec);
+
+    let mut mac = <SipHasher13 as KeyInit>::new(&key.into());
+    Mac::update(&mut mac, b"foo");
+    let first = mac.finalize_fixed_reset();
+    assert_eq!(
+        first[..],
+        SipHasher13::new_with_key(&key).hash(b"foo").as_bytes()
+    );
+    digest::Update::update(&mut mac, b"bar");
+    Mac::reset(&mut mac);
+    Mac::update(&mut mac, b"foo");
+    assert_eq!(mac.finalize_fixed(), first);
+
+    // Nothing buffered before a reset leaks into the next output.
+    let empty = SipHasher13::new_with_key(&key).hash(b"").as_bytes();
+    let mut mac = <SipHasher13 as KeyInit>::new(&key.into());
+    Mac::update(&mut mac, b"bar");
+    Mac::reset(&mut mac);
+    assert_eq!(mac.finalize_fixed()[..], empty);
+    let mut mac = <SipHasher13 as KeyInit>::new(&key.into());
+    Mac::update(&mut mac, b"bar");
+    mac.finalize_fixed_reset();
+    assert_eq!(mac.finalize_fixed()[..], empty);
+}
+
+#[test]
+fn test_siphash128_reset_and_rekey() {
+    const H: (Hash128, Hash128) = {
+        let mut hasher = SipHasher24::new_with_keys(1, 2);
+        hasher.write(b"foo");
+        let first = hasher.finish128_and_reset();
+        hasher.write(b"foo");
+        (first, hasher.finish128())
+    };
+    assert_eq!(H.0, SipHasher24::new_with_keys(1, 2).hash(b"foo"));
+    assert_eq!(H.0, H.1);
+
+    // The bytes buffered before a reset don't leak into the output.
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    hasher.write(b"bar");
+    hasher.reset();
+    assert_eq!(
+        hasher.finish128(),
+        SipHasher13::new_with_keys(1, 2).hash(b"")
+    );
+    let mut hasher = SipHasher24::new_with_keys(1, 2);
+    hasher.write(b"bar");
+    hasher.reset();
+    hasher.write_u8(7);
+    assert_eq!(
+        hasher.finish128(),
+        SipHasher24::new_with_keys(1, 2).hash(&[7])
+    );
+
+    let mut hasher = SipHasher13::new_with_keys(1, 2);
+    hasher.write(&[1; 13]);
+    hasher.reset();
+    hasher.write(b"foo");
+    assert_eq!(
+        hasher.finish_and_reset(),
+        SipHasher13::new_with_keys(1, 2).hash(b"foo").h2
+    );
+    hasher.write(&[1; 13]);
+    hasher.rekey(3, 4);
+    hasher.write(b"bar");
+    assert_eq!(
+        hasher.finish128(),
+        SipHasher13::new_with_keys(3, 4).hash(b"bar")
+    );
+
+    let mut hasher = SipHasher::new();
+    hasher.write(b"bar");
+    hasher.rekey(3, 4);
+    hasher.write(b"foo");
+    assert_eq!(
+        hasher.finish128_and_reset(),
+        SipHasher24::new_with_keys(3, 4).hash(b"foo")
+    );
+    let mut hasher = SipHasherCD::<4, 8>::new();
+    hasher.write(b"bar");
+    hasher.reset();
+    assert_eq!(hasher.finish128(), SipHasherCD::<4, 8>::new().hash(b""));
+}
+
+#[test]
+fn test_siphash128_fixed_size_hashes() {
+    for prefix in [0, 5, 16] {
+        let mut hasher = SipHasher13::new_with_keys(1, 2);
+        hasher.write(&[0x55; 16][..prefix]);
+
+        assert_eq!(
+            hasher.hash_u32(0xdead_beef),
+            hasher.hash(&0xdead_beefu32.to_le_bytes())
+        );
+        assert_eq!(
+            hasher.hash_u64(u64::MAX - 1),
+            hasher.hash(&(u64::MAX - 1).to_le_bytes())
+        );
+        assert_eq!(
+            hasher.hash_u128(u128::MAX / 5),
+            hasher.hash(&(u128::MAX / 5).to_le_bytes())
+        );
+        assert_eq!(hasher.hash_array(&[]), hasher.hash(&[]));
+        assert_eq!(hasher.hash_array(b"0123456"), hasher.hash(b"0123456"));
+        assert_eq!(
+            hasher.hash_array(b"0123456789abcdef!"),
+            hasher.hash(b"0123456789abcdef!")
+        );
+    }
+
+    const H: Hash128 = SipHasher24::new_with_keys(1, 2).hash_u128(42);
+    assert_eq!(
+        H,
+        SipHasher24::new_with_keys(1, 2).hash(&42u128.to_le_bytes())
+    );
+    assert_eq!(
+        SipHasher::new().hash_u32(7),
+        SipHasher24::new().hash(&[7, 0, 0, 0])
+    );
+    assert_eq!(
+        SipHasherCD::<4, 8>::new().hash_u64(7),
+        SipHasherCD::<4, 8>::new().hash(&7u64.to_le_bytes())
+    );
+}
+
+#[test]
+fn test_siphash128_hash_many() {
+    let data: Vec<u8> = (0..100).map(|i| (i * 13) as u8).collect();
+    let lengths = [0, 7, 8, 9, 70, 1, 15, 16, 17, 3, 100];
+    for prefix in [0, 3, 8] {
+        let mut hasher = SipHasher13::new_with_keys(1, 2);
+        hasher.write(&[0xaa; 8][..prefix]);
+
+        for count in 0..=lengths.len() {
+            let inputs: Vec<&[u8]> = lengths[..count].iter().map(|&n| &data[..n]).collect();
+            let expected: Vec<Hash128> = inputs.iter().map(|input| hasher.hash(input)).collect();
+
+            let mut out = vec![Hash128::default(); count];
+            hasher.hash_many(&inputs, &mut out);
+            assert_eq!(out, expected);
+
+            let mut out = vec![Hash128::default(); count];
+            hasher.hash_many_scalar(&inputs, &mut out);
+            assert_eq!(out, expected);
+        }
+    }
+
+    let inputs: [&[u8]; 3] = [b"foo", b"", b"0123456789abcdef0"];
+    let mut out = [Hash128::default(); 3];
+    SipHasher::new().hash_many(&inputs, &mut out);
+    assert_eq!(out[2], SipHasher24::new().hash(inputs[2]));
+    SipHasherCD::<4, 8>::new().hash_many(&inputs, &mut out);
+    assert_eq!(out[0], SipHasherCD::<4, 8>::new().hash(inputs[0]));
+    SipHasher24::new().hash_many_scalar(&inputs, &mut out);
+    assert_eq!(out[1], SipHasher24::new().hash(inputs[1]));
+}
+
+#[test]
+fn test_siphash128_build_hasher() {
+    const BUILDER: SipBuildHasher24 = SipBuildHasher24::new_with_keys(1, 2);
+    let mut hasher = BUILDER.build_hasher();
+    Hasher::write(&mut hasher, b"foo");
+    assert_eq!(
+        hasher.finish128().as_bytes(),
+        SipHasher24::new_with_keys(1, 2).hash(b"foo").as_bytes()
+    );
+
+    let mut set = HashSet::with_hasher(SipBuildHasher13::new_with_keys(1, 2));
+    set.insert(1u32);
+    assert!(set.contains(&1));
+    assert!(!set.contains(&2));
+}
+
+#[test]
+fn test_siphash128_macros() {
+    const KEY: (u64, u64) = (1, 2);
+    const FOO13: [u8; 16] = crate::siphash13_128!(KEY, "foo").as_bytes();
+    const FOOBAR24: [u8; 16] = crate::siphash24_128!(KEY, "foo", b"bar").as_bytes();
+
+    assert_eq!(
+        FOO13,
+        SipHasher13::new_with_keys(1, 2).hash(b"foo").as_bytes()
+    );
+    assert_eq!(
+        FOOBAR24,
+        SipHasher24::new_with_keys(1, 2).hash(b"foobar").as_bytes()
+    );
+}
+
 #[test]
 #[cfg(all(feature = "serde", feature = "serde_json"))]
 fn test_siphash128_serde() {
@@ -132,3 +788,14 @@ fn test_siphash128_serde() {
     let deserialized: [u8; 16] = serde_json::from_str(&serialized).unwrap();
     assert_eq!(hash, deserialized);
 }
+
+#[test]
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+fn test_siphash128_hasher_serde() {
+    let mut hasher = SipHasher24::new_with_keys(1, 2);
+    hasher.write(b"foo");
+    let serialized = serde_json::to_string(&hasher).unwrap();
+    assert!(serialized.starts_with("{\"hasher\":{\"k0\":1,\"k1\":2,\"length\":3,"));
+    let deserialized: SipHasher24 = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.finish128(), hasher.finish128());
+}
@@ -0,0 +1,218 @@
+use super::bloom::BloomFilter;
+#[cfg(feature = "alloc")]
+use super::bloom::GrowableBloomFilter;
+
+fn item(i: u32) -> [u8; 4] {
+    i.to_le_bytes()
+}
+
+/// Returns the share of the items `from..from + count`, never inserted, that
+/// `contains` reports as present.
+fn false_positive_rate(contains: impl Fn(&[u8]) -> bool, from: u32, count: u32) -> f64 {
+    let positives = (from..from + count).filter(|&i| contains(&item(i))).count();
+    positives as f64 / count as f64
+}
+
+#[test]
+fn test_bloom_insert_contains() {
+    let mut filter = BloomFilter::<64, 7>::new_with_keys(1, 2);
+    assert!(filter.is_empty());
+    for i in 0..300 {
+        assert!(filter.insert(&item(i)));
+    }
+    for i in 0..300 {
+        assert!(filter.contains(&item(i)));
+        assert!(!filter.insert(&item(i)));
+    }
+    assert!(!filter.is_empty());
+    assert!(filter.count_ones() <= 300 * 7);
+
+    filter.clear();
+    assert!(filter.is_empty());
+    assert_eq!(filter, BloomFilter::new_with_keys(1, 2));
+}
+
+#[test]
+fn test_bloom_const() {
+    const FILTER: BloomFilter<4, 3> = {
+        let mut filter = BloomFilter::new_with_key(&[7; 16]);
+        filter.insert(b"foo");
+        filter.insert(b"bar");
+        filter
+    };
+    const CONTAINS: [bool; 3] = [
+        FILTER.contains(b"foo"),
+        FILTER.contains(b"bar"),
+        FILTER.contains(b"baz"),
+    ];
+    assert_eq!(CONTAINS, [true, true, false]);
+    assert_eq!(FILTER.count_ones(), 6);
+    assert_eq!(BloomFilter::<4, 3>::BITS, 256);
+}
+
+#[test]
+fn test_bloom_false_positive_rate() {
+    // 16384 bits and 1500 items: the expected rate is about 0.5%.
+    let mut filter = BloomFilter::<256, 7>::new_with_keys(3, 4);
+    for i in 0..1500 {
+        filter.insert(&item(i));
+    }
+    let rate = false_positive_rate(|x| filter.contains(x), 1_000_000, 100_000);
+    assert!(rate > 0.002 && rate < 0.01, "{}", rate);
+}
+
+#[test]
+fn test_bloom_keyed() {
+    let mut a = BloomFilter::<8, 4>::new_with_keys(1, 2);
+    let mut b = BloomFilter::<8, 4>::new_with_keys(2, 1);
+    a.insert(b"foo");
+    b.insert(b"foo");
+    assert_ne!(a.words(), b.words());
+}
+
+#[test]
+fn test_bloom_union() {
+    let mut a = BloomFilter::<32, 5>::new_with_keys(1, 2);
+    let mut b = BloomFilter::<32, 5>::new_with_keys(1, 2);
+    let mut both = BloomFilter::<32, 5>::new_with_keys(1, 2);
+    for i in 0..100 {
+        a.insert(&item(i));
+        both.insert(&item(i));
+    }
+    for i in 100..200 {
+        b.insert(&item(i));
+        both.insert(&item(i));
+    }
+    a.union(&b);
+    assert_eq!(a, both);
+    assert!((0..200).all(|i| a.contains(&item(i))));
+}
+
+#[test]
+#[should_panic(expected = "different keys")]
+fn test_bloom_union_different_keys() {
+    let mut a = BloomFilter::<1, 1>::new_with_keys(1, 2);
+    a.union(&BloomFilter::new_with_keys(1, 3));
+}
+
+#[test]
+fn test_bloom_words_roundtrip() {
+    let mut filter = BloomFilter::<8, 3>::new_with_keys(5, 6);
+    filter.insert(b"foo");
+    let copy = BloomFilter::<8, 3>::from_words(filter.sip_key(), *filter.words());
+    assert_eq!(copy, filter);
+    assert!(copy.contains(b"foo"));
+    assert_eq!(
+        format!("{:?}", copy),
+        "BloomFilter { bits: 512, k: 3, ones: 3, .. }"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+fn test_bloom_serde() {
+    let mut filter = BloomFilter::<4, 3>::new_with_sip_key(super::SipKey::from_u64s(1, 2));
+    filter.insert(b"foo");
+    let serialized = serde_json::to_string(&filter).unwrap();
+    let deserialized: BloomFilter<4, 3> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, filter);
+
+    assert!(serde_json::from_str::<BloomFilter<5, 3>>(&serialized).is_err());
+    assert!(serde_json::from_str::<BloomFilter<4, 0>>(&serialized).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_growable_bloom() {
+    let mut filter = GrowableBloomFilter::new_with_keys(1, 2, 100, 0.01);
+    assert!(filter.is_empty());
+    for i in 0..10_000 {
+        filter.insert(&item(i));
+    }
+    assert!((0..10_000).all(|i| filter.contains(&item(i))));
+    // 100 + 200 + ... + 6400 is the first sum of stages above 10000.
+    assert_eq!(filter.stages(), 7);
+    // Some items were reported as possibly present, and not inserted.
+    assert!(filter.len() > 9_900 && filter.len() <= 10_000);
+
+    let rate = false_positive_rate(|x| filter.contains(x), 1_000_000, 100_000);
+    assert!(rate < 0.01, "{}", rate);
+
+    filter.clear();
+    assert!(filter.is_empty());
+    assert_eq!(filter.stages(), 1);
+    assert!(!filter.contains(&item(1)));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_growable_bloom_union() {
+    let mut a = GrowableBloomFilter::new_with_keys(1, 2, 50, 0.001);
+    let mut b = GrowableBloomFilter::new_with_keys(1, 2, 50, 0.001);
+    for i in 0..100 {
+        a.insert(&item(i));
+    }
+    for i in 100..400 {
+        b.insert(&item(i));
+    }
+    a.union(&b);
+    assert!((0..400).all(|i| a.contains(&item(i))));
+    assert_eq!(a.stages(), b.stages());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "different parameters")]
+fn test_growable_bloom_union_different_parameters() {
+    let mut a = GrowableBloomFilter::new_with_keys(1, 2, 50, 0.01);
+    a.union(&GrowableBloomFilter::new_with_keys(1, 2, 50, 0.001));
+}
+
+#[test]
+#[cfg(all(feature = "alloc", feature = "serde", feature = "serde_json"))]
+fn test_growable_bloom_serde() {
+    let mut filter = GrowableBloomFilter::new(10, 0.01);
+    for i in 0..100 {
+        filter.insert(&item(i));
+    }
+    let serialized = serde_json::to_string(&filter).unwrap();
+    let deserialized: GrowableBloomFilter = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, filter);
+
+    let serialized = serde_json::to_string(&GrowableBloomFilter::new(10, 0.5)).unwrap();
+    assert_eq!(
+        serialized,
+        "{\"key\":{\"k0\":0,\"k1\":0},\"initial_capacity\":10,\"initial_k\":2,\
+         \"stages\":[{\"k\":2,\"capacity\":10,\"len\":0,\"words\":[0]}]}"
+    );
+    for (from, to) in [
+        (
+            "\"stages\":[{\"k\":2,\"capacity\":10,\"len\":0,\"words\":[0]}]",
+            "\"stages\":[]",
+        ),
+        ("\"words\":[0]", "\"words\":[]"),
+        ("\"words\":[0]", "\"words\":[0,0]"),
+        ("\"k\":2", "\"k\":0"),
+        ("\"capacity\":10", "\"capacity\":20"),
+        ("\"len\":0", "\"len\":11"),
+        ("\"initial_k\":2", "\"initial_k\":0"),
+        ("\"initial_capacity\":10", "\"initial_capacity\":0"),
+        (
+            "[0]}]",
+            "[0]},{\"k\":3,\"capacity\":10,\"len\":0,\"words\":[0]}]",
+        ),
+    ] {
+        let invalid = serialized.replace(from, to);
+        assert_ne!(invalid, serialized);
+        assert!(
+            serde_json::from_str::<GrowableBloomFilter>(&invalid).is_err(),
+            "{}",
+            invalid
+        );
+    }
+    let valid = serialized.replace(
+        "[0]}]",
+        "[0]},{\"k\":3,\"capacity\":20,\"len\":0,\"words\":[0,0]}]",
+    );
+    assert!(serde_json::from_str::<GrowableBloomFilter>(&valid).is_ok());
+}
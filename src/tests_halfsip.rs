@@ -0,0 +1,278 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::hash::{Hash, Hasher};
+
+use super::halfsip::{SipHasher, SipHasher13, SipHasher24};
+
+// Hash just the bytes of the slice, without length prefix
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Hash for Bytes<'a> {
+    #[allow(unused_must_use)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Bytes(v) = *self;
+        state.write(v);
+    }
+}
+
+fn hash_with<H: Hasher, T: Hash>(mut st: H, x: &T) -> u64 {
+    x.hash(&mut st);
+    st.finish()
+}
+
+fn hash<T: Hash>(x: &T) -> u64 {
+    hash_with(SipHasher::new(), x)
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn test_halfsiphash_1_3() {
+    // No official vectors exist for 1-3; these come from the reference
+    // implementation built with cROUNDS=1 and dROUNDS=3.
+    let vecs: [[u8; 4]; 64] = [
+        [0x96, 0xc8, 0x14, 0x58],
+        [0xca, 0x64, 0xe8, 0xe7],
+        [0x30, 0x0e, 0x4b, 0xbc],
+        [0x39, 0x99, 0x53, 0x01],
+        [0xa6, 0x9e, 0x05, 0x7e],
+        [0x9b, 0xd8, 0xe3, 0x88],
+        [0x65, 0x0b, 0x08, 0xa0],
+        [0xd6, 0xd9, 0x38, 0x9d],
+        [0xb1, 0x99, 0x79, 0x57],
+        [0xed, 0xca, 0x39, 0xc8],
+        [0xcf, 0x32, 0xfa, 0xe4],
+        [0xee, 0x46, 0x92, 0x95],
+        [0x6c, 0x09, 0x28, 0x6b],
+        [0xd6, 0x9c, 0xdd, 0x66],
+        [0x7c, 0x8a, 0x65, 0x16],
+        [0x04, 0x7b, 0x25, 0xd0],
+        [0x01, 0xd5, 0x31, 0x8b],
+        [0x4b, 0xd0, 0x1c, 0x2b],
+        [0x39, 0x23, 0x71, 0x06],
+        [0x67, 0xca, 0x2a, 0x52],
+        [0x05, 0xb6, 0x1b, 0x91],
+        [0x0e, 0x5f, 0xa6, 0x90],
+        [0x7b, 0xef, 0x26, 0xf8],
+        [0xeb, 0x2d, 0x51, 0x62],
+        [0xd7, 0x0a, 0x15, 0x57],
+        [0x07, 0x35, 0x47, 0x5d],
+        [0x42, 0x74, 0xc4, 0x1e],
+        [0xd3, 0xaf, 0x64, 0xab],
+        [0xd0, 0x00, 0x41, 0x0a],
+        [0x52, 0xe6, 0x2c, 0x6d],
+        [0xa3, 0xb6, 0x31, 0x23],
+        [0x1a, 0x79, 0xd8, 0x08],
+        [0x8d, 0xda, 0x6d, 0xbc],
+        [0x34, 0xc9, 0xf6, 0xe0],
+        [0x33, 0x20, 0x65, 0xb0],
+        [0xcc, 0x51, 0x98, 0x9b],
+        [0x7f, 0xfb, 0x46, 0x7c],
+        [0xcb, 0xa8, 0x2b, 0x73],
+        [0x7a, 0x99, 0x42, 0xf1],
+        [0x1b, 0xaa, 0xc9, 0xfc],
+        [0xb2, 0x7e, 0x32, 0x05],
+        [0x1c, 0x13, 0x10, 0xe1],
+        [0xc0, 0xe7, 0xe5, 0xf9],
+        [0xa6, 0x08, 0xd7, 0xa7],
+        [0xb1, 0x5a, 0x79, 0x11],
+        [0x19, 0x16, 0x67, 0x65],
+        [0x91, 0xff, 0x5f, 0x9f],
+        [0x67, 0x52, 0x9c, 0xd8],
+        [0xeb, 0x83, 0x77, 0x00],
+        [0x43, 0x62, 0x76, 0x95],
+        [0x62, 0x92, 0x63, 0xab],
+        [0x90, 0x13, 0x7e, 0x9c],
+        [0xa6, 0xdd, 0x68, 0xc3],
+        [0x55, 0xc4, 0xdd, 0x38],
+        [0x79, 0xd3, 0x13, 0xfa],
+        [0xe8, 0xa4, 0x9e, 0x97],
+        [0x7e, 0xd7, 0xec, 0x53],
+        [0x57, 0x06, 0xe8, 0x2e],
+        [0x6a, 0xb6, 0xdb, 0x33],
+        [0x77, 0x05, 0x3f, 0xae],
+        [0xcc, 0xc4, 0xb4, 0x88],
+        [0x0b, 0x48, 0x7f, 0x3e],
+        [0xf8, 0xeb, 0xc1, 0x74],
+        [0x04, 0x83, 0x17, 0x87],
+    ];
+
+    let k0 = 0x_03_02_01_00;
+    let k1 = 0x_07_06_05_04;
+    let mut buf = Vec::new();
+    let mut t = 0;
+    let mut state_inc = SipHasher13::new_with_keys(k0, k1);
+
+    while t < 64 {
+        let vec = u32::from_le_bytes(vecs[t]) as u64;
+        let out = hash_with(SipHasher13::new_with_keys(k0, k1), &Bytes(&buf));
+        assert_eq!(vec, out);
+
+        let full = hash_with(SipHasher13::new_with_keys(k0, k1), &Bytes(&buf));
+        let i = state_inc.finish();
+
+        assert_eq!(full, i);
+        assert_eq!(full, vec);
+        assert_eq!(SipHasher13::new_with_keys(k0, k1).hash(&buf) as u64, vec);
+
+        buf.push(t as u8);
+        Hasher::write(&mut state_inc, &[t as u8]);
+
+        t += 1;
+    }
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn test_halfsiphash_2_4() {
+    let vecs: [[u8; 4]; 64] = [
+        [0xa9, 0x35, 0x9f, 0x5b],
+        [0x27, 0x47, 0x5a, 0xb8],
+        [0xfa, 0x62, 0xa6, 0x03],
+        [0x8a, 0xfe, 0xe7, 0x04],
+        [0x2a, 0x6e, 0x46, 0x89],
+        [0xc5, 0xfa, 0xb6, 0x69],
+        [0x58, 0x63, 0xfc, 0x23],
+        [0x8b, 0xcf, 0x63, 0xc5],
+        [0xd0, 0xb8, 0x84, 0x8f],
+        [0xf8, 0x06, 0xe7, 0x79],
+        [0x94, 0xb0, 0x79, 0x34],
+        [0x08, 0x08, 0x30, 0x50],
+        [0x57, 0xf0, 0x87, 0x2f],
+        [0x77, 0xe6, 0x63, 0xff],
+        [0xd6, 0xff, 0xf8, 0x7c],
+        [0x74, 0xfe, 0x2b, 0x97],
+        [0xd9, 0xb5, 0xac, 0x84],
+        [0xc4, 0x74, 0x64, 0x5b],
+        [0x46, 0x5b, 0x8d, 0x9b],
+        [0x7b, 0xef, 0xe3, 0x87],
+        [0xe3, 0x4d, 0x10, 0x45],
+        [0x61, 0x3f, 0x62, 0xb3],
+        [0x70, 0xf3, 0x67, 0xfe],
+        [0xe6, 0xad, 0xb8, 0xbd],
+        [0x27, 0x40, 0x0c, 0x63],
+        [0x26, 0x78, 0x78, 0x75],
+        [0x4f, 0x56, 0x7b, 0x5f],
+        [0x3a, 0xb0, 0xe6, 0x69],
+        [0xb0, 0x64, 0x40, 0x00],
+        [0xff, 0x67, 0x0f, 0xb4],
+        [0x50, 0x9e, 0x33, 0x8b],
+        [0x5d, 0x58, 0x9f, 0x1a],
+        [0xfe, 0xe7, 0x21, 0x12],
+        [0x33, 0x75, 0x32, 0x59],
+        [0x6a, 0x43, 0x4f, 0x8c],
+        [0xfe, 0x28, 0xb7, 0x29],
+        [0xe7, 0x5c, 0xc6, 0xec],
+        [0x69, 0x7e, 0x8d, 0x54],
+        [0x63, 0x68, 0x8b, 0x0f],
+        [0x65, 0x0b, 0x62, 0xb4],
+        [0xb6, 0xbc, 0x18, 0x40],
+        [0x5d, 0x07, 0x45, 0x05],
+        [0x24, 0x42, 0xfd, 0x2e],
+        [0x7b, 0xb7, 0x86, 0x3a],
+        [0x77, 0x05, 0xd5, 0x48],
+        [0xd7, 0x52, 0x08, 0xb1],
+        [0xb6, 0xd4, 0x99, 0xc8],
+        [0x08, 0x92, 0x20, 0x2e],
+        [0x69, 0xe1, 0x2c, 0xe3],
+        [0x8d, 0xb5, 0x80, 0xe5],
+        [0x36, 0x97, 0x64, 0xc6],
+        [0x01, 0x6e, 0x02, 0x04],
+        [0x3b, 0x85, 0xf3, 0xd4],
+        [0xfe, 0xdb, 0x66, 0xbe],
+        [0x1e, 0x69, 0x2a, 0x3a],
+        [0xc6, 0x89, 0x84, 0xc0],
+        [0xa5, 0xc5, 0xb9, 0x40],
+        [0x9b, 0xe9, 0xe8, 0x8c],
+        [0x7d, 0xbc, 0x81, 0x40],
+        [0x7c, 0x07, 0x8e, 0xc5],
+        [0xd4, 0xe7, 0x6c, 0x73],
+        [0x42, 0x8f, 0xcb, 0xb9],
+        [0xbd, 0x83, 0x99, 0x7a],
+        [0x59, 0xea, 0x4a, 0x74],
+    ];
+
+    let k0 = 0x_03_02_01_00;
+    let k1 = 0x_07_06_05_04;
+    let mut buf = Vec::new();
+    let mut t = 0;
+    let mut state_inc = SipHasher24::new_with_keys(k0, k1);
+
+    while t < 64 {
+        let vec = u32::from_le_bytes(vecs[t]) as u64;
+        let out = hash_with(SipHasher24::new_with_keys(k0, k1), &Bytes(&buf));
+        assert_eq!(vec, out);
+
+        let full = hash_with(SipHasher24::new_with_keys(k0, k1), &Bytes(&buf));
+        let i = state_inc.finish();
+
+        assert_eq!(full, i);
+        assert_eq!(full, vec);
+        assert_eq!(SipHasher24::new_with_keys(k0, k1).hash(&buf) as u64, vec);
+
+        buf.push(t as u8);
+        Hasher::write(&mut state_inc, &[t as u8]);
+
+        t += 1;
+    }
+}
+
+#[test]
+fn test_halfsiphash_idempotent() {
+    let val64 = 0xdead_beef_dead_beef_u64;
+    assert_eq!(hash(&val64), hash(&val64));
+    let val32 = 0xdeadbeef_u32;
+    assert_eq!(hash(&val32), hash(&val32));
+}
+
+#[test]
+fn test_halfsiphash_integer_writes_match_bytes() {
+    let mut a = SipHasher24::new_with_keys(1, 2);
+    a.write_u8(0x11);
+    a.write_u16(0x2233);
+    a.write_u32(0x4455_6677);
+    a.write_u64(0x8899_aabb_ccdd_eeff);
+    a.write_usize(0x1234);
+
+    let mut b = SipHasher24::new_with_keys(1, 2);
+    b.write(&0x11u8.to_ne_bytes());
+    b.write(&0x2233u16.to_ne_bytes());
+    b.write(&0x4455_6677u32.to_ne_bytes());
+    b.write(&0x8899_aabb_ccdd_eeffu64.to_ne_bytes());
+    b.write(&0x1234usize.to_ne_bytes());
+
+    assert_eq!(a.finish(), b.finish());
+}
+
+#[test]
+fn test_halfsiphash_key() {
+    let key: &[u8; 8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+    const HASHER: SipHasher13 = SipHasher13::new_with_key(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(HASHER.keys(), (0x04030201, 0x08070605));
+    assert_eq!(&HASHER.key(), key);
+    assert_eq!(
+        SipHasher13::new_with_key(key).hash(b"abc"),
+        HASHER.hash(b"abc")
+    );
+}
+
+#[test]
+fn test_halfsiphash_incremental() {
+    let array1: &[u8] = &[1, 2, 3];
+    let array2: &[u8] = &[4, 5, 6];
+    let key: &[u8; 8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+    let mut hasher = SipHasher13::new_with_key(key);
+    hasher.write(array1);
+    hasher.write(array2);
+    assert_eq!(
+        hasher.finish(),
+        SipHasher13::new_with_key(key).hash(&[1, 2, 3, 4, 5, 6]) as u64
+    );
+}
@@ -0,0 +1,278 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::hash::{Hash, Hasher};
+
+use super::halfsip64::{SipHasher, SipHasher13, SipHasher24};
+
+// Hash just the bytes of the slice, without length prefix
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Hash for Bytes<'a> {
+    #[allow(unused_must_use)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Bytes(v) = *self;
+        state.write(v);
+    }
+}
+
+fn hash_with<H: Hasher, T: Hash>(mut st: H, x: &T) -> u64 {
+    x.hash(&mut st);
+    st.finish()
+}
+
+fn hash<T: Hash>(x: &T) -> u64 {
+    hash_with(SipHasher::new(), x)
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn test_halfsiphash64_1_3() {
+    // No official vectors exist for 1-3; these come from the reference
+    // implementation built with cROUNDS=1 and dROUNDS=3.
+    let vecs: [[u8; 8]; 64] = [
+        [0x76, 0xa5, 0xd0, 0x21, 0x23, 0x20, 0xf7, 0x2a],
+        [0x87, 0xe8, 0x74, 0x8d, 0x6f, 0xd3, 0x33, 0x97],
+        [0x78, 0xee, 0x1f, 0xe1, 0x5d, 0x3c, 0xd7, 0x32],
+        [0x3e, 0x52, 0x39, 0xa3, 0x6b, 0xde, 0x6c, 0x3e],
+        [0xeb, 0x24, 0xdb, 0xa0, 0x71, 0x8c, 0xae, 0x05],
+        [0x83, 0xe7, 0x46, 0x4e, 0xcb, 0xc3, 0xbd, 0xe6],
+        [0xaf, 0x65, 0xe0, 0xc6, 0x4d, 0xf5, 0xd6, 0x02],
+        [0x3c, 0x05, 0x2d, 0x0a, 0xb2, 0x4c, 0xf1, 0x8f],
+        [0x3d, 0xcd, 0xa8, 0x31, 0x1d, 0xec, 0x7a, 0x5f],
+        [0xbd, 0x61, 0x5f, 0xbf, 0xc1, 0x43, 0x3b, 0xf6],
+        [0x31, 0x34, 0xfb, 0xa8, 0x09, 0xe0, 0x41, 0xf2],
+        [0xcd, 0x3d, 0xaa, 0x72, 0xc2, 0x0b, 0x76, 0xfb],
+        [0x55, 0x77, 0xcc, 0x5c, 0xb3, 0xe7, 0x3f, 0x93],
+        [0x6d, 0x17, 0x7c, 0xca, 0x97, 0xc2, 0x25, 0x44],
+        [0x7d, 0xa9, 0x15, 0xca, 0x92, 0xec, 0x82, 0x6b],
+        [0x16, 0x5b, 0xdf, 0xa6, 0x26, 0xa2, 0x72, 0x9a],
+        [0x56, 0xd7, 0xc5, 0xb9, 0x3d, 0x3e, 0x2e, 0xc2],
+        [0x58, 0xec, 0x4c, 0x13, 0xd6, 0xac, 0x3a, 0x30],
+        [0xf4, 0x49, 0xa1, 0x8a, 0xd5, 0x98, 0x7a, 0xd6],
+        [0x7d, 0x12, 0x90, 0xed, 0xf3, 0x42, 0x9e, 0xf8],
+        [0x52, 0x5e, 0x23, 0x29, 0x4d, 0x74, 0x08, 0xe4],
+        [0xc3, 0xd9, 0xb8, 0x86, 0x7f, 0xd9, 0x3b, 0xe5],
+        [0xaf, 0x97, 0xb5, 0x3a, 0xe6, 0x6d, 0x9b, 0xda],
+        [0x70, 0xd7, 0xde, 0x31, 0x94, 0x36, 0xae, 0xff],
+        [0x85, 0x81, 0x92, 0xab, 0xe6, 0xc2, 0x53, 0x24],
+        [0x6d, 0x56, 0x7e, 0xb6, 0xb6, 0xf9, 0xe3, 0xa8],
+        [0x4a, 0x51, 0xbf, 0xce, 0x4c, 0x82, 0xf9, 0xf3],
+        [0x8e, 0x4f, 0xc3, 0x2e, 0x7b, 0xd9, 0xc8, 0x2a],
+        [0xb1, 0xbc, 0xbf, 0x18, 0xc2, 0xf4, 0x63, 0x6a],
+        [0xec, 0x74, 0x01, 0xc4, 0x8b, 0x68, 0x9e, 0x2a],
+        [0xad, 0x30, 0x15, 0xd8, 0x57, 0x89, 0x3d, 0xdd],
+        [0xcb, 0xbb, 0x9e, 0x1b, 0xde, 0xca, 0xb6, 0x32],
+        [0x16, 0x6e, 0xee, 0x0f, 0xc1, 0x84, 0xb8, 0xf1],
+        [0xbf, 0xb7, 0x14, 0x95, 0xd4, 0xb6, 0x6b, 0xf4],
+        [0x41, 0x35, 0x9e, 0x00, 0x34, 0xcf, 0xd7, 0x1a],
+        [0x38, 0x3c, 0x31, 0xd4, 0x75, 0x9a, 0x33, 0xfe],
+        [0xec, 0x90, 0x55, 0x53, 0xa4, 0x3d, 0x1e, 0xa4],
+        [0xa7, 0xcc, 0x17, 0x4d, 0xb7, 0x30, 0xd6, 0x67],
+        [0xa2, 0xdf, 0xb7, 0x76, 0xb8, 0x3a, 0xa5, 0x8e],
+        [0x74, 0x93, 0xd3, 0x77, 0xc7, 0xcb, 0x46, 0x49],
+        [0x52, 0x85, 0x67, 0xd5, 0x9d, 0xd5, 0xea, 0x12],
+        [0xc1, 0x9f, 0xa8, 0xfc, 0x02, 0x4c, 0x13, 0x3e],
+        [0xbb, 0x18, 0xd5, 0x3d, 0xe3, 0x3a, 0x01, 0xa4],
+        [0x3e, 0xd8, 0x2d, 0x7e, 0x59, 0x43, 0xff, 0x03],
+        [0x69, 0x0a, 0xb3, 0x3f, 0x13, 0x71, 0x44, 0x89],
+        [0x27, 0x75, 0x10, 0xb7, 0x96, 0xa7, 0x4b, 0x74],
+        [0x47, 0xcf, 0xa4, 0xb8, 0x06, 0x05, 0x3f, 0x1c],
+        [0x59, 0x4c, 0xec, 0xf6, 0xfe, 0xfb, 0xff, 0x14],
+        [0xfc, 0x51, 0x3a, 0x57, 0xf2, 0x3c, 0x39, 0x03],
+        [0x39, 0x89, 0x75, 0xe7, 0xa1, 0x92, 0xee, 0x36],
+        [0x42, 0xbd, 0x3b, 0x40, 0x2e, 0xa0, 0x9f, 0x40],
+        [0x36, 0x5a, 0xd6, 0x2a, 0xc3, 0xbc, 0x8a, 0xcb],
+        [0x85, 0xf2, 0x76, 0x51, 0x2b, 0xd3, 0x0d, 0x97],
+        [0x63, 0xd3, 0x1f, 0x16, 0x71, 0xb1, 0x93, 0x01],
+        [0xa2, 0xe7, 0x21, 0x50, 0x37, 0x78, 0xe4, 0x29],
+        [0x8b, 0xbf, 0xba, 0x9e, 0x90, 0xfe, 0x0a, 0x8a],
+        [0xd7, 0xdd, 0x10, 0xb7, 0xec, 0xfd, 0x87, 0x3a],
+        [0x93, 0x6c, 0x4e, 0x9f, 0x93, 0xc2, 0x59, 0x7b],
+        [0x8b, 0x01, 0x46, 0xec, 0x86, 0xc5, 0x1f, 0x3c],
+        [0x5c, 0x9d, 0x18, 0x31, 0x60, 0xdd, 0x82, 0x91],
+        [0x49, 0xab, 0x1b, 0xc3, 0x94, 0xc2, 0x74, 0xf4],
+        [0xc2, 0x45, 0xb4, 0x26, 0x8e, 0x3f, 0xdd, 0x4d],
+        [0x7c, 0xdf, 0x3a, 0xe3, 0xb2, 0xde, 0x57, 0x16],
+        [0x3c, 0x12, 0xc4, 0x20, 0x19, 0x14, 0xc5, 0xc1],
+    ];
+
+    let k0 = 0x_03_02_01_00;
+    let k1 = 0x_07_06_05_04;
+    let mut buf = Vec::new();
+    let mut t = 0;
+    let mut state_inc = SipHasher13::new_with_keys(k0, k1);
+
+    while t < 64 {
+        let vec = u64::from_le_bytes(vecs[t]);
+        let out = hash_with(SipHasher13::new_with_keys(k0, k1), &Bytes(&buf));
+        assert_eq!(vec, out);
+
+        let full = hash_with(SipHasher13::new_with_keys(k0, k1), &Bytes(&buf));
+        let i = state_inc.finish();
+
+        assert_eq!(full, i);
+        assert_eq!(full, vec);
+        assert_eq!(SipHasher13::new_with_keys(k0, k1).hash(&buf), vec);
+
+        buf.push(t as u8);
+        Hasher::write(&mut state_inc, &[t as u8]);
+
+        t += 1;
+    }
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn test_halfsiphash64_2_4() {
+    let vecs: [[u8; 8]; 64] = [
+        [0x21, 0x8d, 0x1f, 0x59, 0xb9, 0xb8, 0x3c, 0xc8],
+        [0xbe, 0x55, 0x24, 0x12, 0xf8, 0x38, 0x73, 0x15],
+        [0x06, 0x4f, 0x39, 0xef, 0x7c, 0x50, 0xeb, 0x57],
+        [0xce, 0x0f, 0x1a, 0x45, 0xf7, 0x06, 0x06, 0x79],
+        [0xd5, 0xe7, 0x8a, 0x17, 0x5b, 0xe5, 0x2e, 0xa1],
+        [0xcb, 0x9d, 0x7c, 0x3f, 0x2f, 0x3d, 0xb5, 0x80],
+        [0xce, 0x3e, 0x91, 0x35, 0x8a, 0xa2, 0xbc, 0x25],
+        [0xff, 0x20, 0x27, 0x28, 0xb0, 0x7b, 0xc6, 0x84],
+        [0xed, 0xfe, 0xe8, 0x20, 0xbc, 0xe4, 0x85, 0x8c],
+        [0x5b, 0x51, 0xcc, 0xcc, 0x13, 0x88, 0x83, 0x07],
+        [0x95, 0xb0, 0x46, 0x9f, 0x06, 0xa6, 0xf2, 0xee],
+        [0xae, 0x26, 0x33, 0x39, 0x94, 0xdd, 0xcd, 0x48],
+        [0x7b, 0xc7, 0x1f, 0x9f, 0xae, 0xf5, 0xc7, 0x99],
+        [0x5a, 0x23, 0x52, 0xd7, 0x5a, 0x0c, 0x37, 0x44],
+        [0x3b, 0xb1, 0xa8, 0x70, 0xea, 0xe8, 0xe6, 0x58],
+        [0x21, 0x7d, 0x0b, 0xcb, 0x4e, 0x81, 0xc9, 0x02],
+        [0x73, 0x36, 0xaa, 0xd2, 0x5f, 0x7b, 0xf3, 0xb5],
+        [0x37, 0xad, 0xc0, 0x64, 0x1c, 0x4c, 0x4f, 0x6a],
+        [0xc9, 0xb2, 0xdb, 0x2b, 0x9a, 0x3e, 0x42, 0xf9],
+        [0xf9, 0x10, 0xe4, 0x80, 0x20, 0xab, 0x36, 0x3c],
+        [0x1b, 0xf5, 0x2b, 0x0a, 0x6f, 0xee, 0xa7, 0xdb],
+        [0x00, 0x74, 0x1d, 0xc2, 0x69, 0xe8, 0xb3, 0xef],
+        [0xe2, 0x01, 0x03, 0xfa, 0x1b, 0xa7, 0x76, 0xef],
+        [0x4c, 0x22, 0x10, 0xe5, 0x4b, 0x68, 0x1d, 0x73],
+        [0x70, 0x74, 0x10, 0x45, 0xae, 0x3f, 0xa6, 0xf1],
+        [0x0c, 0x86, 0x40, 0x37, 0x39, 0x71, 0x40, 0x38],
+        [0x0d, 0x89, 0x9e, 0xd8, 0x11, 0x29, 0x23, 0xf0],
+        [0x22, 0x6b, 0xf5, 0xfa, 0xb8, 0x1e, 0xe1, 0xb8],
+        [0x2d, 0x92, 0x5f, 0xfb, 0x1e, 0x00, 0x16, 0xb5],
+        [0x36, 0x19, 0x58, 0xd5, 0x2c, 0xee, 0x10, 0xf1],
+        [0x29, 0x1a, 0xaf, 0x86, 0x48, 0x98, 0x17, 0x9d],
+        [0x86, 0x3c, 0x7f, 0x15, 0x5c, 0x34, 0x11, 0x7c],
+        [0x28, 0x70, 0x9d, 0x46, 0xd8, 0x11, 0x62, 0x6c],
+        [0x24, 0x84, 0x77, 0x68, 0x1d, 0x28, 0xf8, 0x9c],
+        [0x83, 0x24, 0xe4, 0xd7, 0x52, 0x8f, 0x98, 0x30],
+        [0xf9, 0xef, 0xd4, 0xe1, 0x3a, 0xea, 0x6b, 0xd8],
+        [0x86, 0xd6, 0x7a, 0x40, 0xec, 0x42, 0x76, 0xdc],
+        [0x3f, 0x62, 0x92, 0xec, 0xcc, 0xa9, 0x7e, 0x35],
+        [0xcb, 0xd9, 0x2e, 0xe7, 0x24, 0xd4, 0x21, 0x09],
+        [0x36, 0x8d, 0xf6, 0x80, 0x8d, 0x40, 0x3d, 0x79],
+        [0x5b, 0x38, 0xc8, 0x1c, 0x67, 0xc8, 0xae, 0x4c],
+        [0x95, 0xab, 0x71, 0x89, 0xd4, 0x39, 0xac, 0xb3],
+        [0xa9, 0x1a, 0x52, 0xc0, 0x25, 0x32, 0x70, 0x24],
+        [0x5b, 0x00, 0x87, 0xc6, 0x95, 0x28, 0xac, 0xea],
+        [0x1e, 0x30, 0xf3, 0xad, 0x27, 0xdc, 0xb1, 0x5a],
+        [0x69, 0x7f, 0x5c, 0x9a, 0x90, 0x32, 0x4e, 0xd4],
+        [0x49, 0x5c, 0x0f, 0x99, 0x55, 0x57, 0xdc, 0x38],
+        [0x94, 0x27, 0x20, 0x2a, 0x3c, 0x29, 0xf9, 0x4d],
+        [0xa9, 0xea, 0xa8, 0xc0, 0x4b, 0xa9, 0x3e, 0x3e],
+        [0xee, 0xa4, 0xc1, 0x73, 0x7d, 0x01, 0x12, 0x18],
+        [0x91, 0x2d, 0x56, 0x8f, 0xd8, 0xf6, 0x5a, 0x49],
+        [0x56, 0x91, 0x95, 0x96, 0xb0, 0xff, 0x5c, 0x97],
+        [0x02, 0x44, 0x5a, 0x79, 0x98, 0xf5, 0x50, 0xe1],
+        [0x86, 0xec, 0x46, 0x6c, 0xe7, 0x1d, 0x1f, 0xb2],
+        [0x35, 0x95, 0x69, 0xe7, 0xd2, 0x89, 0xe3, 0xbc],
+        [0x87, 0x1b, 0x05, 0xca, 0x62, 0xbb, 0x7c, 0x96],
+        [0xa1, 0xa4, 0x92, 0xf9, 0x42, 0xf1, 0x5f, 0x1d],
+        [0x12, 0xec, 0x26, 0x7f, 0xf6, 0x09, 0x5b, 0x6e],
+        [0x5d, 0x1b, 0x5e, 0xa1, 0xb2, 0x31, 0xd8, 0x9d],
+        [0xd8, 0xcf, 0xb4, 0x45, 0x3f, 0x92, 0xee, 0x54],
+        [0xd6, 0x76, 0x28, 0x90, 0xbf, 0x26, 0xe4, 0x60],
+        [0x31, 0x35, 0x63, 0xa4, 0xb7, 0xed, 0x5c, 0xf3],
+        [0xf9, 0x0b, 0x3a, 0xb5, 0x72, 0xd4, 0x66, 0x93],
+        [0x2e, 0xa6, 0x3c, 0x71, 0xbf, 0x32, 0x60, 0x87],
+    ];
+
+    let k0 = 0x_03_02_01_00;
+    let k1 = 0x_07_06_05_04;
+    let mut buf = Vec::new();
+    let mut t = 0;
+    let mut state_inc = SipHasher24::new_with_keys(k0, k1);
+
+    while t < 64 {
+        let vec = u64::from_le_bytes(vecs[t]);
+        let out = hash_with(SipHasher24::new_with_keys(k0, k1), &Bytes(&buf));
+        assert_eq!(vec, out);
+
+        let full = hash_with(SipHasher24::new_with_keys(k0, k1), &Bytes(&buf));
+        let i = state_inc.finish();
+
+        assert_eq!(full, i);
+        assert_eq!(full, vec);
+        assert_eq!(SipHasher24::new_with_keys(k0, k1).hash(&buf), vec);
+
+        buf.push(t as u8);
+        Hasher::write(&mut state_inc, &[t as u8]);
+
+        t += 1;
+    }
+}
+
+#[test]
+fn test_halfsiphash64_idempotent() {
+    let val64 = 0xdead_beef_dead_beef_u64;
+    assert_eq!(hash(&val64), hash(&val64));
+    let val32 = 0xdeadbeef_u32;
+    assert_eq!(hash(&val32), hash(&val32));
+}
+
+#[test]
+fn test_halfsiphash64_integer_writes_match_bytes() {
+    let mut a = SipHasher24::new_with_keys(1, 2);
+    a.write_u8(0x11);
+    a.write_u16(0x2233);
+    a.write_u32(0x4455_6677);
+    a.write_u64(0x8899_aabb_ccdd_eeff);
+    a.write_usize(0x1234);
+
+    let mut b = SipHasher24::new_with_keys(1, 2);
+    b.write(&0x11u8.to_ne_bytes());
+    b.write(&0x2233u16.to_ne_bytes());
+    b.write(&0x4455_6677u32.to_ne_bytes());
+    b.write(&0x8899_aabb_ccdd_eeffu64.to_ne_bytes());
+    b.write(&0x1234usize.to_ne_bytes());
+
+    assert_eq!(a.finish(), b.finish());
+}
+
+#[test]
+fn test_halfsiphash64_key() {
+    let key: &[u8; 8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+    const HASHER: SipHasher13 = SipHasher13::new_with_key(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(HASHER.keys(), (0x04030201, 0x08070605));
+    assert_eq!(&HASHER.key(), key);
+    assert_eq!(
+        SipHasher13::new_with_key(key).hash(b"abc"),
+        HASHER.hash(b"abc")
+    );
+}
+
+#[test]
+fn test_halfsiphash64_incremental() {
+    let array1: &[u8] = &[1, 2, 3];
+    let array2: &[u8] = &[4, 5, 6];
+    let key: &[u8; 8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+    let mut hasher = SipHasher13::new_with_key(key);
+    hasher.write(array1);
+    hasher.write(array2);
+    assert_eq!(
+        hasher.finish(),
+        SipHasher13::new_with_key(key).hash(&[1, 2, 3, 4, 5, 6])
+    );
+}
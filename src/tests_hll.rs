@@ -0,0 +1,191 @@
+use super::hll::HyperLogLog;
+use super::sip::SipHasher13;
+
+fn relative_error(hll: &HyperLogLog, n: u64) -> f64 {
+    (hll.estimate() - n as f64).abs() / n as f64
+}
+
+#[test]
+fn test_hll_empty() {
+    let hll = HyperLogLog::new_with_keys(1, 2, 12);
+    assert!(hll.is_empty());
+    assert!(hll.is_sparse());
+    assert_eq!(hll.estimate(), 0.0);
+}
+
+#[test]
+fn test_hll_accuracy() {
+    // The standard error is 1.04 / sqrt(2^12) = 1.6%: allow 3 of them.
+    let mut hll = HyperLogLog::new_with_keys(1, 2, 12);
+    let mut n = 0u64;
+    for checkpoint in [10, 100, 1_000, 10_000, 100_000, 200_000] {
+        while n < checkpoint {
+            hll.insert(&n.to_le_bytes());
+            n += 1;
+        }
+        let tolerance = if hll.is_sparse() { 0.01 } else { 0.05 };
+        assert!(
+            relative_error(&hll, n) < tolerance,
+            "{} items, estimated {}",
+            n,
+            hll.estimate()
+        );
+    }
+    assert!(!hll.is_sparse());
+}
+
+#[test]
+fn test_hll_duplicates() {
+    let mut hll = HyperLogLog::new_with_keys(1, 2, 10);
+    for _ in 0..10 {
+        for i in 0..5_000u32 {
+            hll.insert(&i.to_le_bytes());
+        }
+    }
+    assert!(relative_error(&hll, 5_000) < 0.1, "{}", hll.estimate());
+}
+
+#[test]
+fn test_hll_sparse_to_dense() {
+    // The sparse list takes 4 bytes per entry, and the 2^8 dense registers 1
+    // byte each.
+    let mut hll = HyperLogLog::new_with_keys(1, 2, 8);
+    for i in 0..64u32 {
+        hll.insert(&i.to_le_bytes());
+    }
+    assert!(hll.is_sparse());
+    hll.insert(&64u32.to_le_bytes());
+    assert!(!hll.is_sparse());
+    assert!(relative_error(&hll, 65) < 0.2, "{}", hll.estimate());
+
+    hll.clear();
+    assert!(hll.is_empty());
+    assert!(hll.is_sparse());
+}
+
+#[test]
+fn test_hll_large_sparse() {
+    // At the largest precision, the sketch stays sparse up to 65536 entries.
+    let mut hll = HyperLogLog::new_with_keys(1, 2, 18);
+    for i in 0..60_000u32 {
+        hll.insert(&i.to_le_bytes());
+    }
+    assert!(hll.is_sparse());
+    assert!(relative_error(&hll, 60_000) < 0.01, "{}", hll.estimate());
+    for i in 60_000..70_000u32 {
+        hll.insert(&i.to_le_bytes());
+    }
+    assert!(!hll.is_sparse());
+    assert!(relative_error(&hll, 70_000) < 0.01, "{}", hll.estimate());
+}
+
+#[test]
+fn test_hll_duplicates_at_threshold() {
+    // 2^12 dense registers take as much room as 1024 sparse entries.
+    let mut hll = HyperLogLog::new_with_keys(1, 2, 12);
+    for i in 0..1_020u32 {
+        hll.insert(&i.to_le_bytes());
+    }
+    assert!(hll.is_sparse());
+    // Duplicates just below the limit densify the sketch, instead of being
+    // sorted into the list again and again.
+    for _ in 0..5 {
+        hll.insert(&7u32.to_le_bytes());
+    }
+    assert!(!hll.is_sparse());
+    assert!(relative_error(&hll, 1_020) < 0.05, "{}", hll.estimate());
+}
+
+#[test]
+fn test_hll_buffered_entries() {
+    // Entries still buffered count in the estimate, comparisons and merges.
+    let mut a = HyperLogLog::new_with_keys(1, 2, 14);
+    let mut b = HyperLogLog::new_with_keys(1, 2, 14);
+    for i in 0..1_000u32 {
+        a.insert(&i.to_le_bytes());
+    }
+    for i in (0..1_000u32).rev() {
+        b.insert(&i.to_le_bytes());
+        b.insert(&i.to_le_bytes());
+    }
+    a.insert(b"foo");
+    assert_ne!(a, b);
+    b.insert(b"foo");
+    assert_eq!(a, b);
+    assert!(relative_error(&a, 1_001) < 0.01, "{}", a.estimate());
+
+    let mut c = HyperLogLog::new_with_keys(1, 2, 14);
+    c.insert(b"bar");
+    c.merge(&a);
+    a.insert(b"bar");
+    assert_eq!(c, a);
+}
+
+#[test]
+fn test_hll_insert_hash() {
+    let mut a = HyperLogLog::new_with_keys(3, 4, 10);
+    let mut b = HyperLogLog::new_with_keys(3, 4, 10);
+    let hasher = SipHasher13::new_with_keys(3, 4);
+    for i in 0..1_000u32 {
+        a.insert(&i.to_le_bytes());
+        assert_eq!(a.hash(&i.to_le_bytes()), hasher.hash(&i.to_le_bytes()));
+        b.insert_hash(hasher.hash(&i.to_le_bytes()));
+    }
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_hll_merge() {
+    // All the combinations of sparse and dense sketches give the same
+    // registers as inserting everything into one sketch.
+    for (count_a, count_b) in [(10u64, 20u64), (10, 5_000), (5_000, 10), (5_000, 7_000)] {
+        let mut a = HyperLogLog::new_with_keys(1, 2, 12);
+        let mut b = HyperLogLog::new_with_keys(1, 2, 12);
+        let mut all = HyperLogLog::new_with_keys(1, 2, 12);
+        for i in 0..count_a {
+            a.insert(&i.to_le_bytes());
+            all.insert(&i.to_le_bytes());
+        }
+        for i in 1_000_000..1_000_000 + count_b {
+            b.insert(&i.to_le_bytes());
+            all.insert(&i.to_le_bytes());
+        }
+        a.merge(&b);
+        assert_eq!(a, all);
+        assert!(relative_error(&a, count_a + count_b) < 0.05);
+    }
+}
+
+#[test]
+#[should_panic(expected = "can't be merged")]
+fn test_hll_merge_different_keys() {
+    let mut a = HyperLogLog::new_with_keys(1, 2, 12);
+    a.merge(&HyperLogLog::new_with_keys(2, 1, 12));
+}
+
+#[test]
+#[should_panic(expected = "precision")]
+fn test_hll_invalid_precision() {
+    HyperLogLog::new(19);
+}
+
+#[test]
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+fn test_hll_serde() {
+    for n in [10u32, 1_000, 10_000] {
+        let mut hll = HyperLogLog::new_with_keys(1, 2, 10);
+        for i in 0..n {
+            hll.insert(&i.to_le_bytes());
+        }
+        let serialized = serde_json::to_string(&hll).unwrap();
+        let deserialized: HyperLogLog = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, hll);
+        assert_eq!(deserialized.estimate(), hll.estimate());
+    }
+
+    let serialized = serde_json::to_string(&HyperLogLog::new(10)).unwrap();
+    let invalid = serialized.replace("\"precision\":10", "\"precision\":30");
+    assert!(serde_json::from_str::<HyperLogLog>(&invalid).is_err());
+    let invalid = serialized.replace("\"Sparse\":[]", "\"Dense\":[0]");
+    assert!(serde_json::from_str::<HyperLogLog>(&invalid).is_err());
+}
@@ -0,0 +1,110 @@
+use super::phf::{ConstMap, ConstSet};
+
+const KEYWORDS: [&str; 51] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+const fn numbered<const N: usize>(keys: [&'static str; N]) -> [(&'static str, usize); N] {
+    let mut entries = [("", 0); N];
+    let mut i = 0;
+    while i < N {
+        entries[i] = (keys[i], i);
+        i += 1;
+    }
+    entries
+}
+
+const MAP: ConstMap<usize, 51> = ConstMap::new(numbered(KEYWORDS));
+const SET: ConstSet<51> = ConstSet::new(KEYWORDS);
+
+#[test]
+fn test_const_map_get() {
+    for (i, key) in KEYWORDS.iter().enumerate() {
+        assert_eq!(MAP.get(key), Some(&i), "{}", key);
+        assert_eq!(MAP.get_index(key), Some(i));
+        assert_eq!(MAP.get_entry(key), Some((*key, &i)));
+        assert!(MAP.contains_key(key));
+    }
+    for key in ["", "a", "iff", "SELF", "selff", "union", "asynchronous"] {
+        assert_eq!(MAP.get(key), None, "{}", key);
+        assert!(!MAP.contains_key(key));
+    }
+    assert_eq!(MAP.len(), 51);
+    assert!(!MAP.is_empty());
+}
+
+#[test]
+fn test_const_map_in_const() {
+    const LET: Option<&usize> = MAP.get("let");
+    const UNION: Option<usize> = MAP.get_index("union");
+    assert_eq!(LET, Some(&17));
+    assert_eq!(UNION, None);
+}
+
+#[test]
+fn test_const_map_iter() {
+    assert!(MAP.keys().eq(KEYWORDS.iter().copied()));
+    assert!(MAP.values().copied().eq(0..51));
+    assert!(MAP.iter().eq(KEYWORDS
+        .iter()
+        .copied()
+        .zip(&numbered(KEYWORDS).map(|e| e.1))));
+    assert_eq!((&MAP).into_iter().next_back(), Some(("try", &50)));
+    assert_eq!(MAP.iter().len(), 51);
+    assert_eq!(MAP.entries(), &numbered(KEYWORDS));
+}
+
+#[test]
+fn test_const_map_small() {
+    const EMPTY: ConstMap<u8, 0> = ConstMap::new([]);
+    assert_eq!(EMPTY.get("a"), None);
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.iter().next(), None);
+
+    const ONE: ConstMap<u8, 1> = ConstMap::new([("a", 1)]);
+    assert_eq!(ONE.get("a"), Some(&1));
+    assert_eq!(ONE.get("b"), None);
+
+    assert_eq!(format!("{:?}", ONE), r#"{"a": 1}"#);
+}
+
+#[test]
+fn test_const_map_runtime() {
+    let keys: Vec<String> = (0..200).map(|i| format!("key{}", i)).collect();
+    let keys: &'static [String] = Box::leak(keys.into_boxed_slice());
+    let mut entries = [("", 0); 200];
+    for (i, key) in keys.iter().enumerate() {
+        entries[i] = (key.as_str(), i);
+    }
+    let map = ConstMap::new(entries);
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(map.get(key), Some(&i));
+    }
+    assert_eq!(map.get("key200"), None);
+}
+
+#[test]
+#[should_panic(expected = "duplicate key")]
+fn test_const_map_duplicate() {
+    ConstMap::new([("a", 1), ("b", 2), ("a", 3)]);
+}
+
+#[test]
+fn test_const_set() {
+    for key in KEYWORDS {
+        assert!(SET.contains(key));
+        assert_eq!(SET.get(key), Some(key));
+    }
+    assert!(!SET.contains("union"));
+    assert_eq!(SET.get("union"), None);
+    assert_eq!(SET.get_index("fn"), Some(12));
+    assert!(SET.iter().eq(KEYWORDS.iter().copied()));
+    assert_eq!(SET.len(), 51);
+
+    const SMALL: ConstSet<2> = ConstSet::new(["x", "y"]);
+    assert_eq!(format!("{:?}", SMALL), r#"{"x", "y"}"#);
+}
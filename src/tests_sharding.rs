@@ -0,0 +1,307 @@
+#[cfg(feature = "alloc")]
+use super::sharding::HashRing;
+use super::sharding::{
+    jump_consistent_hash, rendezvous_pick, rendezvous_score, weighted_rendezvous_pick,
+};
+use super::sip::SipHasher24;
+
+const KEYS: u32 = 100_000;
+
+fn key(i: u32) -> [u8; 4] {
+    i.to_le_bytes()
+}
+
+/// Checks that every count is within `tolerance` (relative) of `expected`.
+fn assert_close(counts: &[u32], expected: &[f64], tolerance: f64) {
+    for (&count, &expected) in counts.iter().zip(expected) {
+        let error = (count as f64 - expected).abs() / expected;
+        assert!(error < tolerance, "{:?}, expected {:?}", counts, expected);
+    }
+}
+
+#[test]
+fn test_rendezvous_uniform() {
+    let hasher = SipHasher24::new_with_keys(1, 2);
+    let nodes: Vec<String> = (0..10).map(|i| format!("node-{}", i)).collect();
+    let mut counts = [0; 10];
+    for i in 0..KEYS {
+        let node = rendezvous_pick(&hasher, &key(i), &nodes).unwrap();
+        counts[nodes.iter().position(|n| n == node).unwrap()] += 1;
+    }
+    assert_close(&counts, &[KEYS as f64 / 10.0; 10], 0.05);
+}
+
+#[test]
+fn test_rendezvous_removal() {
+    let hasher = SipHasher24::new_with_keys(1, 2);
+    let nodes = ["a", "b", "c", "d", "e"];
+    let fewer = ["a", "b", "d", "e"];
+    for i in 0..10_000 {
+        let before = rendezvous_pick(&hasher, &key(i), &nodes).unwrap();
+        let after = rendezvous_pick(&hasher, &key(i), &fewer).unwrap();
+        if *before != "c" {
+            assert_eq!(before, after);
+        }
+    }
+}
+
+#[test]
+fn test_rendezvous_keyed() {
+    let a = SipHasher24::new_with_keys(1, 2);
+    let b = SipHasher24::new_with_keys(2, 1);
+    let nodes = ["a", "b", "c", "d"];
+    let moved = (0..1_000)
+        .filter(|&i| rendezvous_pick(&a, &key(i), &nodes) != rendezvous_pick(&b, &key(i), &nodes))
+        .count();
+    // With independent placements, 3/4 of the keys land elsewhere.
+    assert!(moved > 650 && moved < 850, "{}", moved);
+}
+
+#[test]
+fn test_rendezvous_score() {
+    let hasher = SipHasher24::new_with_keys(1, 2);
+    assert_ne!(
+        rendezvous_score(&hasher, b"a", b"bc"),
+        rendezvous_score(&hasher, b"ab", b"c")
+    );
+    const SCORE: u64 = rendezvous_score(&SipHasher24::new_with_keys(1, 2), b"node", b"key");
+    assert_eq!(SCORE, rendezvous_score(&hasher, b"node", b"key"));
+
+    let no_nodes: [&str; 0] = [];
+    assert_eq!(rendezvous_pick(&hasher, b"key", &no_nodes), None);
+}
+
+#[test]
+fn test_weighted_rendezvous() {
+    let hasher = SipHasher24::new_with_keys(3, 4);
+    let nodes = [("a", 1.0), ("b", 2.0), ("c", 3.0), ("d", 4.0), ("off", 0.0)];
+    let mut counts = [0; 5];
+    for i in 0..KEYS {
+        let node = weighted_rendezvous_pick(&hasher, &key(i), &nodes).unwrap();
+        counts[nodes.iter().position(|(n, _)| n == node).unwrap()] += 1;
+    }
+    assert_eq!(counts[4], 0);
+    let expected: Vec<f64> = (1..=4).map(|w| KEYS as f64 * w as f64 / 10.0).collect();
+    assert_close(&counts[..4], &expected, 0.05);
+
+    let off = [
+        ("a", 0.0),
+        ("b", -1.0),
+        ("c", f64::NAN),
+        ("d", f64::INFINITY),
+    ];
+    assert_eq!(weighted_rendezvous_pick(&hasher, b"key", &off), None);
+}
+
+#[test]
+fn test_weighted_rendezvous_equal_weights() {
+    // With equal weights, the scores are in the same order as the hashes.
+    let hasher = SipHasher24::new_with_keys(5, 6);
+    let nodes = ["a", "b", "c"];
+    let weighted = [("a", 2.5), ("b", 2.5), ("c", 2.5)];
+    for i in 0..1_000 {
+        assert_eq!(
+            rendezvous_pick(&hasher, &key(i), &nodes),
+            weighted_rendezvous_pick(&hasher, &key(i), &weighted)
+        );
+    }
+}
+
+#[test]
+fn test_ln() {
+    for &x in &[
+        1e-300,
+        2.0f64.powi(-53),
+        1e-5,
+        0.1,
+        0.5,
+        0.7,
+        1.4,
+        1.5,
+        0.999999,
+        1.0,
+        2.0,
+        1e10,
+    ] {
+        let error = (super::sharding::ln(x) - x.ln()).abs();
+        assert!(
+            error <= 1e-15 * x.ln().abs().max(1.0),
+            "ln({}): {}",
+            x,
+            error
+        );
+    }
+}
+
+#[test]
+fn test_jump_consistent_hash() {
+    let hasher = SipHasher24::new_with_keys(1, 2);
+    let mut counts = [0; 7];
+    for i in 0..KEYS {
+        let h = hasher.hash(&key(i));
+        let bucket = jump_consistent_hash(h, 7);
+        counts[bucket as usize] += 1;
+
+        // Growing from n to n + 1 buckets only moves keys to bucket n.
+        let mut previous = 0;
+        for n in 1..20 {
+            let bucket = jump_consistent_hash(h, n);
+            assert!(bucket < n);
+            assert!(bucket == previous || bucket == n - 1);
+            previous = bucket;
+        }
+    }
+    assert_close(&counts, &[KEYS as f64 / 7.0; 7], 0.05);
+
+    const BUCKET: u32 = jump_consistent_hash(42, 1);
+    assert_eq!(BUCKET, 0);
+}
+
+#[test]
+#[should_panic(expected = "at least one bucket")]
+fn test_jump_consistent_hash_no_buckets() {
+    jump_consistent_hash(42, 0);
+}
+
+#[cfg(feature = "alloc")]
+fn ring(nodes: usize, vnodes: u32) -> HashRing<String> {
+    let mut ring = HashRing::new_with_keys(1, 2, vnodes);
+    for i in 0..nodes {
+        assert!(ring.add(format!("node-{}", i)));
+    }
+    ring
+}
+
+#[cfg(feature = "alloc")]
+fn ring_placement(ring: &HashRing<String>) -> Vec<String> {
+    (0..KEYS)
+        .map(|i| ring.get(&key(i)).unwrap().clone())
+        .collect()
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_ring_uniform() {
+    let ring = ring(10, 160);
+    assert_eq!(ring.len(), 10);
+    let mut counts = [0; 10];
+    for node in ring_placement(&ring) {
+        counts[ring.nodes().iter().position(|n| *n == node).unwrap()] += 1;
+    }
+    assert_close(&counts, &[KEYS as f64 / 10.0; 10], 0.25);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_ring_add() {
+    let mut ring = ring(10, 160);
+    let before = ring_placement(&ring);
+    assert!(ring.add("node-10".to_string()));
+    assert!(!ring.add("node-10".to_string()));
+    let after = ring_placement(&ring);
+
+    // Only keys moving to the new node move, about 1/11 of them.
+    let mut moved = 0;
+    for (before, after) in before.iter().zip(&after) {
+        if before != after {
+            assert_eq!(after, "node-10");
+            moved += 1;
+        }
+    }
+    let share = moved as f64 / KEYS as f64;
+    println!(
+        "adding an 11th node moved {} keys ({:.2}%)",
+        moved,
+        share * 100.0
+    );
+    assert!(share > 0.06 && share < 0.125, "{}", share);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_ring_remove() {
+    let mut ring = ring(10, 160);
+    let before = ring_placement(&ring);
+    assert_eq!(ring.remove("node-3"), Some("node-3".to_string()));
+    assert_eq!(ring.remove("node-3"), None);
+    assert!(!ring.contains("node-3"));
+    assert_eq!(ring.len(), 9);
+    let after = ring_placement(&ring);
+
+    // Only the keys of the removed node move.
+    let mut moved = 0;
+    for (before, after) in before.iter().zip(&after) {
+        if before == "node-3" {
+            moved += 1;
+        } else {
+            assert_eq!(before, after);
+        }
+    }
+    let share = moved as f64 / KEYS as f64;
+    println!(
+        "removing 1 of 10 nodes moved {} keys ({:.2}%)",
+        moved,
+        share * 100.0
+    );
+    assert!(share > 0.075 && share < 0.125, "{}", share);
+
+    // Adding the node back restores the original placement.
+    ring.add("node-3".to_string());
+    assert_eq!(ring_placement(&ring), before);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_ring_order() {
+    // The ring doesn't depend on the order the nodes were added in.
+    let forward = ring(50, 20);
+    let mut backward = HashRing::new_with_keys(1, 2, 20);
+    for node in forward.nodes().iter().rev() {
+        backward.add(node.clone());
+    }
+    for i in 0..10_000 {
+        assert_eq!(forward.get(&key(i)), backward.get(&key(i)));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_ring_get_n() {
+    let ring = ring(5, 40);
+    for i in 0..1_000 {
+        let nodes = ring.get_n(&key(i), 3);
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[0], ring.get(&key(i)).unwrap());
+        assert!(nodes[0] != nodes[1] && nodes[1] != nodes[2] && nodes[0] != nodes[2]);
+    }
+    assert_eq!(ring.get_n(b"foo", 10).len(), 5);
+    assert!(HashRing::<&str>::new(1).get_n(b"foo", 2).is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_ring_keyed() {
+    let a = ring(4, 100);
+    let mut b = HashRing::new_with_keys(2, 1, 100);
+    for node in a.nodes() {
+        b.add(node.clone());
+    }
+    let moved = (0..1_000)
+        .filter(|&i| a.get(&key(i)) != b.get(&key(i)))
+        .count();
+    // With independent placements, 3/4 of the keys land elsewhere.
+    assert!(moved > 650 && moved < 850, "{}", moved);
+
+    assert_eq!(HashRing::<&str>::new(1).get(b"foo"), None);
+    assert_eq!(
+        format!("{:?}", ring(2, 8)),
+        "HashRing { vnodes: 8, nodes: [\"node-0\", \"node-1\"], .. }"
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "at least one virtual node")]
+fn test_ring_no_vnodes() {
+    HashRing::<&str>::new(0);
+}
@@ -0,0 +1,246 @@
+use core::hash;
+
+/// A structured value that the hashers can hash in a `const` context.
+///
+/// `core::hash::Hash` can't be called from a `const fn`, so composite keys are
+/// described with this type instead, and hashed with the `hash_value` and
+/// `write_value` methods of the hashers:
+///
+/// ```rust
+/// use const_siphasher::sip::SipHasher13;
+/// use const_siphasher::{ConstHash, HashValue};
+///
+/// const H: u64 = SipHasher13::new_with_keys(1, 2)
+///     .hash_value(&HashValue::Tuple(&[HashValue::U32(7), HashValue::Str("foo")]));
+///
+/// let mut hasher = SipHasher13::new_with_keys(1, 2);
+/// (7u32, "foo").const_hash(&mut hasher);
+/// assert_eq!(hasher.finish(), H);
+/// ```
+///
+/// Every value is encoded the same way as by its [`ConstHash`]
+/// implementation, so the result is the same as the runtime path:
+///
+/// * integers are written in little-endian byte order, and `usize`/`isize`
+///   are widened to 64 bits so the result doesn't depend on the platform;
+/// * `bool` is written as a single byte, `0` or `1`;
+/// * `char` is written as its scalar value, like a `u32`;
+/// * strings, byte slices and slices are prefixed with their length as a
+///   `u64`, so that `("ab", "c")` and `("a", "bc")` hash differently;
+/// * arrays and tuples are written as their elements in order, with no
+///   prefix since their length is part of their type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashValue<'a> {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    Bool(bool),
+    Char(char),
+    /// A string, prefixed with its length in bytes.
+    Str(&'a str),
+    /// A byte slice, prefixed with its length.
+    Bytes(&'a [u8]),
+    /// A slice, prefixed with its number of elements.
+    Slice(&'a [HashValue<'a>]),
+    /// A fixed-size array, written as its elements.
+    Array(&'a [HashValue<'a>]),
+    /// A tuple, written as its fields.
+    Tuple(&'a [HashValue<'a>]),
+}
+
+/// Feeds a value into a [`Hasher`](hash::Hasher) with a portable encoding.
+///
+/// Unlike `core::hash::Hash`, the encoding doesn't depend on the platform
+/// and matches the one used for [`HashValue`], so a key hashed at runtime
+/// with this trait gives the same result as the equivalent `HashValue`
+/// hashed at compile time.
+pub trait ConstHash {
+    /// Feeds this value into the given hasher.
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H);
+}
+
+macro_rules! impl_const_hash_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ConstHash for $ty {
+                #[inline]
+                fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+                    state.write(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_const_hash_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl ConstHash for usize {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        (*self as u64).const_hash(state);
+    }
+}
+
+impl ConstHash for isize {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        (*self as i64).const_hash(state);
+    }
+}
+
+impl ConstHash for bool {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        (*self as u8).const_hash(state);
+    }
+}
+
+impl ConstHash for char {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        (*self as u32).const_hash(state);
+    }
+}
+
+impl ConstHash for str {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.len().const_hash(state);
+        state.write(self.as_bytes());
+    }
+}
+
+impl<T: ConstHash> ConstHash for [T] {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.len().const_hash(state);
+        for item in self {
+            item.const_hash(state);
+        }
+    }
+}
+
+impl<T: ConstHash, const N: usize> ConstHash for [T; N] {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        for item in self {
+            item.const_hash(state);
+        }
+    }
+}
+
+impl<T: ConstHash + ?Sized> ConstHash for &T {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        (**self).const_hash(state);
+    }
+}
+
+/// Written like an enum deriving `PortableSipHash`:
+/// a little-endian `u32`, `0` for `None` and `1` for `Some`, followed by the
+/// value if there is one.
+impl<T: ConstHash> ConstHash for Option<T> {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        match self {
+            None => 0u32.const_hash(state),
+            Some(value) => {
+                1u32.const_hash(state);
+                value.const_hash(state);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ConstHash for alloc::string::String {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().const_hash(state);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ConstHash> ConstHash for alloc::vec::Vec<T> {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().const_hash(state);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ConstHash + ?Sized> ConstHash for alloc::boxed::Box<T> {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        (**self).const_hash(state);
+    }
+}
+
+impl ConstHash for () {
+    #[inline]
+    fn const_hash<H: hash::Hasher>(&self, _state: &mut H) {}
+}
+
+macro_rules! impl_const_hash_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: ConstHash),+> ConstHash for ($($name,)+) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+                let ($($name,)+) = self;
+                $($name.const_hash(state);)+
+            }
+        }
+    };
+}
+
+impl_const_hash_tuple!(A);
+impl_const_hash_tuple!(A B);
+impl_const_hash_tuple!(A B C);
+impl_const_hash_tuple!(A B C D);
+impl_const_hash_tuple!(A B C D E);
+impl_const_hash_tuple!(A B C D E F);
+impl_const_hash_tuple!(A B C D E F G);
+impl_const_hash_tuple!(A B C D E F G H2);
+impl_const_hash_tuple!(A B C D E F G H2 I);
+impl_const_hash_tuple!(A B C D E F G H2 I J);
+impl_const_hash_tuple!(A B C D E F G H2 I J K);
+impl_const_hash_tuple!(A B C D E F G H2 I J K L);
+
+impl ConstHash for HashValue<'_> {
+    fn const_hash<H: hash::Hasher>(&self, state: &mut H) {
+        match *self {
+            HashValue::U8(i) => i.const_hash(state),
+            HashValue::U16(i) => i.const_hash(state),
+            HashValue::U32(i) => i.const_hash(state),
+            HashValue::U64(i) => i.const_hash(state),
+            HashValue::U128(i) => i.const_hash(state),
+            HashValue::Usize(i) => i.const_hash(state),
+            HashValue::I8(i) => i.const_hash(state),
+            HashValue::I16(i) => i.const_hash(state),
+            HashValue::I32(i) => i.const_hash(state),
+            HashValue::I64(i) => i.const_hash(state),
+            HashValue::I128(i) => i.const_hash(state),
+            HashValue::Isize(i) => i.const_hash(state),
+            HashValue::Bool(b) => b.const_hash(state),
+            HashValue::Char(c) => c.const_hash(state),
+            HashValue::Str(s) => s.const_hash(state),
+            HashValue::Bytes(bytes) => bytes.const_hash(state),
+            HashValue::Slice(items) => items.const_hash(state),
+            HashValue::Array(items) | HashValue::Tuple(items) => {
+                for item in items {
+                    item.const_hash(state);
+                }
+            }
+        }
+    }
+}